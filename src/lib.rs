@@ -72,30 +72,65 @@
 //!  to create a struct-specific builder. It's all created automatically.
 //!
 //! ```
-//! #[test]
-//! fn test_group_students_by_grade() {
-//!     // sets up a vec of students with 10 students per number grade
-//!     let students = Student::tlayuda()                  // create tlayuda test builder
-//!         .set_final_grade(|index| (index % 101) as u32) // returns a 0-100 grade based on index
-//!         .build_vec(200);                               // creates a vec of 200 students
-//!
-//!     // call function we're testing
-//!     let result = group_students_by_grade(students);
-//!
-//!     // verifies expected # of students per group
-//!     assert_eq!(20, result.a_students.len());
-//!     assert_eq!(20, result.b_students.len());
-//!     assert_eq!(20, result.c_students.len());
-//!     assert_eq!(20, result.d_students.len());
-//!     assert_eq!(102, result.f_students.len());
-//!
-//!     // verifies every group has the correct grade range
-//!     result.a_students.iter().for_each(|s| assert!(s.final_grade >= 90 && s.final_grade <= 100));
-//!     result.b_students.iter().for_each(|s| assert!(s.final_grade >= 80 && s.final_grade < 90));
-//!     result.c_students.iter().for_each(|s| assert!(s.final_grade >= 70 && s.final_grade < 80));
-//!     result.d_students.iter().for_each(|s| assert!(s.final_grade >= 60 && s.final_grade < 70));
-//!     result.f_students.iter().for_each(|s| assert!(s.final_grade <= 50));
-//! }
+//! # use crate::tlayuda::*;
+//! #   pub struct StudentsPartitionedByGrade {
+//! #       a_students: Vec::<Student>,
+//! #       b_students: Vec::<Student>,
+//! #       c_students: Vec::<Student>,
+//! #       d_students: Vec::<Student>,
+//! #       f_students: Vec::<Student>,
+//! #   }
+//! #   #[derive(Tlayuda)]
+//! #   pub struct Student {
+//! #       id: u64,
+//! #       first_name: String,
+//! #       last_name: String,
+//! #       telephone: String,
+//! #       date_of_birth: String,
+//! #       final_grade: u32,
+//! #   }
+//! #   fn group_students_by_grade(mut students: Vec<Student>) -> StudentsPartitionedByGrade {
+//! #       let result = StudentsPartitionedByGrade {
+//! #           a_students: Vec::new(),
+//! #           b_students: Vec::new(),
+//! #           c_students: Vec::new(),
+//! #           d_students: Vec::new(),
+//! #           f_students: Vec::new(),
+//! #       };
+//! #       students.drain(..).fold(result, |mut acc, student| {
+//! #           match student.final_grade {
+//! #               90..=100 => acc.a_students.push(student),
+//! #               80..=89 => acc.b_students.push(student),
+//! #               70..=79 => acc.c_students.push(student),
+//! #               60..=69 => acc.d_students.push(student),
+//! #               0..=50 => acc.f_students.push(student),
+//! #               _ => (),
+//! #           }
+//! #           acc
+//! #       })
+//! #   }
+//! /* inside a test */
+//! // sets up a vec of students with 10 students per number grade
+//! let students = Student::tlayuda()                  // create tlayuda test builder
+//!     .set_final_grade(|index| (index % 101) as u32) // returns a 0-100 grade based on index
+//!     .build_vec(200);                               // creates a vec of 200 students
+//!
+//! // call function we're testing
+//! let result = group_students_by_grade(students);
+//!
+//! // verifies expected # of students per group
+//! assert_eq!(20, result.a_students.len());
+//! assert_eq!(20, result.b_students.len());
+//! assert_eq!(20, result.c_students.len());
+//! assert_eq!(20, result.d_students.len());
+//! assert_eq!(102, result.f_students.len());
+//!
+//! // verifies every group has the correct grade range
+//! result.a_students.iter().for_each(|s| assert!(s.final_grade >= 90 && s.final_grade <= 100));
+//! result.b_students.iter().for_each(|s| assert!(s.final_grade >= 80 && s.final_grade < 90));
+//! result.c_students.iter().for_each(|s| assert!(s.final_grade >= 70 && s.final_grade < 80));
+//! result.d_students.iter().for_each(|s| assert!(s.final_grade >= 60 && s.final_grade < 70));
+//! result.f_students.iter().for_each(|s| assert!(s.final_grade <= 50));
 //! ```
 //! # How To Use
 //! Add the Tlayuda derive macro above a struct.
@@ -126,8 +161,6 @@
 //! #       last_name: String,
 //! #       is_active: bool
 //! #   }
-//! # #[test]
-//! # fn test() {
 //! /* inside a test */
 //! let mut builder = Person::tlayuda();
 //! let person = builder.build();
@@ -145,7 +178,6 @@
 //! assert_eq!("first_name1", person.first_name);
 //! assert_eq!("last_name1", person.last_name);
 //! assert_eq!(false, person.is_active);
-//! # }
 //! ```
 //!
 //! The builder will also have a `set_` prefixed method for each field in the struct that 
@@ -163,8 +195,6 @@
 //! #       last_name: String,
 //! #       is_active: bool
 //! #   }
-//! # #[test]
-//! # fn test() {
 //! /* inside a test */
 //! let mut builder = Person::tlayuda()
 //!     .set_first_name(|i| {
@@ -180,7 +210,6 @@
 //!  
 //! let person = builder.build();
 //! assert_eq!("Michael", person.first_name);
-//! # }
 //! ```
 //! 
 //! The builder can also generate a `Vec::<_>` of the struct with a call to `build_vec`. 
@@ -196,8 +225,6 @@
 //! #       last_name: String,
 //! #       is_active: bool
 //! #   }
-//! # #[test]
-//! # fn test() {
 //! // builds 1000 Person objects and verifies
 //! // each object has a unique first_name value
 //! Person::tlayuda()
@@ -208,7 +235,6 @@
 //!     .for_each(|(i, x)| {
 //!         assert_eq!(i.to_string(), x.first_name);
 //!     });
-//! # }
 //! ```
 //!
 //! You can also change the starting index of the builder with a call to 
@@ -224,8 +250,6 @@
 //! #       last_name: String,
 //! #       is_active: bool
 //! #   }
-//! # #[test]
-//! # fn test() {
 //! /* inside a test */
 //! Person::tlayuda()
 //!     .set_first_name(|i| match (i % 3, i % 5) {
@@ -235,15 +259,44 @@
 //!         _ => i.to_string(),
 //!     })
 //!     .with_index(1)
-//!     .build_vec(100)
-//! # }
+//!     .build_vec(100);
+//! ```
+//!
+//! Every builder implements the `TlayudaBuilder` trait, whose `Output` is the type being
+//! built, and types without ignored fields implement the `Tlayuda` trait, whose `tlayuda()`
+//! returns their builder. These allow test helpers to be written once for any type.
+//!
+//! ```
+//! #   use crate::tlayuda::*;
+//! #   #[derive(Tlayuda)]
+//! #   pub struct Person {
+//! #       id: u32,
+//! #       first_name: String,
+//! #       last_name: String,
+//! #       is_active: bool
+//! #   }
+//! fn build_batch<B: TlayudaBuilder>(mut builder: B) -> Vec<B::Output> {
+//!     builder.build_vec(10)
+//! }
+//!
+//! fn first<T: Tlayuda>() -> T {
+//!     T::tlayuda().build()
+//! }
+//!
+//! /* inside a test */
+//! let people = build_batch(Person::tlayuda().with_index(100));
+//! assert_eq!(100, people[0].id);
+//!
+//! let person = first::<Person>();
+//! assert_eq!(0, person.id);
 //! ```
 //!
 //! Tlayuda will also automatically attempt to recursively build fields if they're 
 //! not one of the known supported types. That is, if `struct A` has a field that 
 //! is `struct B` which also has the Tlayuda derive macro, the `struct A` builder 
 //! will automatically call `struct B`'s builder. Note: this will cause compile 
-//! errors if the inner struct has unsupported fields or doesn't use the Tlayuda macro.
+//! errors if the inner struct has unsupported fields or doesn't use the Tlayuda macro
+//! (or implement `TlayudaDefault`, see below).
 //!
 //! ```
 //! # use crate::tlayuda::*;
@@ -258,12 +311,517 @@
 //!     pub field_on_b: String,
 //! }
 //!
-//! # #[test]
-//! # fn test() {
 //! /* inside a test */
 //! let some_A = StructA::tlayuda().build();
-//! assert_eq!("field_on_b0", some_A.another_struct.field_on_struct_b);
-//! # }
+//! assert_eq!("field_on_b0", some_A.another_struct.field_on_b);
+//! ```
+//!
+//! Enums can derive Tlayuda as well. The generated builder picks the variant at
+//! `index % variant_count`, so `build_vec` spreads across every variant without any
+//! setup and enum fields on other Tlayuda structs are built automatically. Adding
+//! `tlayuda_ignore` above a variant excludes it from being generated.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda, Debug, PartialEq)]
+//! pub enum OrderStatus {
+//!     Pending,
+//!     Shipped,
+//!     #[tlayuda_ignore]
+//!     Lost,
+//!     Delivered,
+//! }
+//!
+//! #[derive(Tlayuda)]
+//! pub struct Order {
+//!     pub id: u32,
+//!     pub status: OrderStatus,
+//! }
+//!
+//! /* inside a test */
+//! let orders = Order::tlayuda().build_vec(4);
+//! assert_eq!(OrderStatus::Pending, orders[0].status);
+//! assert_eq!(OrderStatus::Shipped, orders[1].status);
+//! assert_eq!(OrderStatus::Delivered, orders[2].status);
+//! assert_eq!(OrderStatus::Pending, orders[3].status);
+//! ```
+//!
+//! Variants that carry data are populated the same way struct fields are. The builder
+//! also gets a `variant_{name}` method to always build a specific variant along with
+//! `set_{variant}_{field}` methods for each payload field. Fields on tuple variants are
+//! named by their position, i.e. `set_renamed_0`.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda, Debug, PartialEq)]
+//! pub enum Event {
+//!     Created { id: u64, name: String },
+//!     Renamed(u64, String),
+//!     Archived,
+//! }
+//!
+//! /* inside a test */
+//! let event = Event::tlayuda()
+//!     .variant_created()
+//!     .set_created_name(|i| format!("event{}", i))
+//!     .build();
+//!
+//! assert_eq!(Event::Created { id: 0, name: "event0".to_string() }, event);
+//! ```
+//!
+//! Tuple structs are supported as well. Their `set_` functions are named by the
+//! field's position, i.e. `set_0`, unless a name is given with the `name` attribute.
+//! A newtype delegates to its inner type's dynamic default, so `UserId(u64)` is
+//! built as `UserId(0)`, `UserId(1)` and so on.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct UserId(u64);
+//!
+//! #[derive(Tlayuda)]
+//! pub struct Score(String, #[tlayuda(name = "points")] u32);
+//!
+//! /* inside a test */
+//! let score = Score::tlayuda()
+//!     .set_0(|i| format!("player{}", i))
+//!     .set_points(|_| 100)
+//!     .build();
+//!
+//! assert_eq!("player0", score.0);
+//! assert_eq!(100, score.1);
+//! ```
+//!
+//! Generic structs and enums are supported. The builder carries the same type and
+//! const parameters and where-clauses as the source type, i.e. `TlayudaPageBuilder<T>`
+//! for `Page<T>`. Fields whose type is a type parameter are built with
+//! `T::tlayuda_default` so the builder requires `T: TlayudaDefault` (see below). Any
+//! other bounds the builder needs, such as ones required by a nested generic Tlayuda
+//! struct, can be declared with the `bound` attribute.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Page<T> {
+//!     pub items: Vec<T>,
+//!     pub first: T,
+//!     pub total: usize,
+//! }
+//!
+//! #[derive(Tlayuda)]
+//! #[tlayuda(bound = "T: tlayuda::runtime::TlayudaDefault")]
+//! pub struct Response<T> {
+//!     pub page: Page<T>,
+//! }
+//!
+//! /* inside a test */
+//! let response = Response::<u32>::tlayuda().build();
+//! assert_eq!(0, response.page.first);
+//! ```
+//!
+//! Structs with lifetimes can borrow `str`, slices and other supported types. Since
+//! the builder has nowhere to keep generated data, borrowed fields are opt-in through
+//! the `leak` attribute (on the struct or on a single field), which backs each value
+//! with a leaked allocation using the same dynamic defaults as owned fields. This is
+//! fine for tests but means the memory is never freed. Slices default to being empty
+//! and don't need the attribute.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! #[tlayuda(leak)]
+//! pub struct Row<'a> {
+//!     pub name: &'a str,
+//!     pub tags: &'a [u32],
+//! }
+//!
+//! /* inside a test */
+//! let row = Row::tlayuda().build();
+//! assert_eq!("name0", row.name);
+//! assert_eq!(0, row.tags.len());
+//! ```
+//!
+//! The standard collections (`Vec`, `VecDeque`, `LinkedList`, `BinaryHeap`, `HashMap`,
+//! `BTreeMap`, `HashSet` and `BTreeSet`) default to being empty. Adding the `len`
+//! attribute populates a collection with that many elements instead. Each element is
+//! built from its type's own dynamic default using an index derived from the built
+//! object's index and the element's position (`index * len + position`), so values
+//! stay unique across every built object. Map keys and values are named with `_key`
+//! and `_value` suffixes.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! # use std::collections::HashMap;
+//! #[derive(Tlayuda)]
+//! pub struct Report {
+//!     #[tlayuda(len = 3)]
+//!     pub totals: Vec<u32>,
+//!     #[tlayuda(len = 2)]
+//!     pub scores: HashMap<String, u32>,
+//! }
+//!
+//! /* inside a test */
+//! let report = Report::tlayuda().with_index(1).build();
+//! assert_eq!(vec![3, 4, 5], report.totals);
+//! assert_eq!(Some(&2), report.scores.get("scores_key2"));
+//! ```
+//!
+//! Using `len` on a collection of Tlayuda structs builds each child with the child's
+//! own builder, so nested children don't need the `set_` workaround of sharing a child
+//! builder between closures. Since each child's index is derived from its parent's,
+//! ids stay unique across the whole generated tree. Populated collections also get a
+//! `set_{field}_each` function taking a closure of the form `Fn(usize, usize) -> Element`
+//! that receives the parent's index and the child's position.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Classroom {
+//!     pub id: u32,
+//!     #[tlayuda(len = 3)]
+//!     pub students: Vec<Student>,
+//! }
+//!
+//! #[derive(Tlayuda)]
+//! pub struct Student {
+//!     pub id: u32,
+//!     pub name: String,
+//! }
+//!
+//! /* inside a test */
+//! let classrooms = Classroom::tlayuda().build_vec(2);
+//! assert_eq!(4, classrooms[1].students[1].id);
+//!
+//! let classroom = Classroom::tlayuda()
+//!     .set_students_each(|classroom, position| {
+//!         Student::tlayuda()
+//!             .set_name(move |_| format!("classroom{}-student{}", classroom, position))
+//!             .build()
+//!     })
+//!     .build();
+//! assert_eq!("classroom0-student2", classroom.students[2].name);
+//! ```
+//!
+//! Smart pointers and cells (`Box`, `Rc`, `Arc`, `Cell`, `RefCell`, `Mutex` and
+//! `RwLock`) are seen through. The inner value is built with its own dynamic default,
+//! or its Tlayuda builder, and then wrapped, so a field like `Arc<Mutex<Config>>` works
+//! without `tlayuda_ignore`.
+//!
+//! Types that hold themselves stop after one level. An optional field like
+//! `next: Option<Box<Node>>` within `Node` starts out as `None`, and an enum field like the
+//! `Box<Expr>` of `Expr::Neg` is built from one of the variants that don't hold an `Expr`,
+//! so `Expr::tlayuda().build_vec(2)` gives `[Lit(0), Neg(Box::new(Lit(1)))]`.
+//!
+//! Tuple fields, like `(String, u32)`, build each element with its own dynamic default.
+//! String-like elements are named with their position, so the first element of a `pair`
+//! field at index 3 is `"pair_0_3"`. The field's `set_` function replaces the whole tuple.
+//!
+//! Arrays can hold any supported type, including nested arrays like `[[f32; 4]; 4]`, and
+//! their length can be any const expression such as `[u8; SIZE]` or `[T; N]`. Each
+//! element is built with an index derived from the built object's index and the element's
+//! position (`index * len + position`), so `[u32; 3]` at index 1 is `[3, 4, 5]`.
+//!
+//! Common filesystem, network and time types also get dynamic defaults based on the index:
+//!
+//! * `PathBuf` (and `Path` behind a reference or smart pointer) is named like a String, i.e. `"config_path0"`
+//! * `Duration` is `index` seconds
+//! * `SystemTime` is the unix epoch plus `index` seconds
+//! * `Ipv4Addr`, `Ipv6Addr` and `IpAddr` are made from the index's bytes, i.e. index 258 is `0.0.1.2`
+//! * `SocketAddr`, `SocketAddrV4` and `SocketAddrV6` use the same address with the index as the port
+//!
+//! Fields of a type Tlayuda doesn't have a built in default for are built through the
+//! [`TlayudaDefault`](runtime::TlayudaDefault) trait, which receives the index of the object
+//! being built and the field's name. Every type deriving Tlayuda without ignored fields
+//! implements it, which is how nested Tlayuda structs are built. Implement it for your
+//! own types, or for a foreign type through a local newtype, to use them in Tlayuda
+//! structs without a builder of their own.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! use tlayuda::runtime::TlayudaDefault;
+//!
+//! pub struct Sku(String);
+//!
+//! impl TlayudaDefault for Sku {
+//!     fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+//!         Sku(format!("SKU-{:04}", index))
+//!     }
+//! }
+//!
+//! #[derive(Tlayuda)]
+//! pub struct Product {
+//!     pub sku: Sku,
+//!     pub replaces: Option<Sku>,
+//! }
+//!
+//! /* inside a test */
+//! let product = Product::tlayuda().with_index(12).build();
+//! assert_eq!("SKU-0012", product.sku.0);
+//! assert_eq!("SKU-0012", product.replaces.unwrap().0);
+//! ```
+//!
+//! A field's dynamic default can be replaced with the `default` attribute, which takes
+//! either a closure of the form `Fn(usize) -> Type` or an expression that's evaluated
+//! with the index in scope as `i`. The generated `set_` methods still override it. Since it
+//! replaces the whole value, an `Option` field's `default` returns the Option itself and
+//! can't be combined with the `option` attribute.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Customer {
+//!     #[tlayuda(default = |i| format!("user{}@example.com", i))]
+//!     pub email: String,
+//!     #[tlayuda(default = 42)]
+//!     pub age: u8,
+//!     #[tlayuda(default = i * 100)]
+//!     pub points: usize,
+//! }
+//!
+//! /* inside a test */
+//! let customer = Customer::tlayuda().with_index(3).build();
+//! assert_eq!("user3@example.com", customer.email);
+//! assert_eq!(42, customer.age);
+//! assert_eq!(300, customer.points);
+//!
+//! let customer = Customer::tlayuda().set_age(|_| 18).build();
+//! assert_eq!(18, customer.age);
+//! ```
+//!
+//! Defaults that are shared across tests can live in a function that a field points to
+//! with the `with` attribute. The function either takes the index, `fn(usize) -> T`, or
+//! the index and the field's name, `fn(usize, &str) -> T`. This also allows fields of
+//! types Tlayuda doesn't support to be generated without `tlayuda_ignore`.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! mod support {
+//!     pub fn email(index: usize) -> String {
+//!         format!("user{}@corp.test", index)
+//!     }
+//!
+//!     pub fn label(index: usize, field_name: &str) -> String {
+//!         format!("{}-{}", field_name.to_uppercase(), index)
+//!     }
+//!
+//!     pub fn callback(_index: usize) -> fn(u32) -> u32 {
+//!         |x| x + 1
+//!     }
+//! }
+//!
+//! #[derive(Tlayuda)]
+//! pub struct Subscriber {
+//!     #[tlayuda(with = support::email)]
+//!     pub email: String,
+//!     #[tlayuda(with = support::label)]
+//!     pub label: String,
+//!     #[tlayuda(with = support::callback)]
+//!     pub on_notify: fn(u32) -> u32,
+//! }
+//!
+//! /* inside a test */
+//! let subscriber = Subscriber::tlayuda().with_index(2).build();
+//! assert_eq!("user2@corp.test", subscriber.email);
+//! assert_eq!("LABEL-2", subscriber.label);
+//! assert_eq!(2, (subscriber.on_notify)(1));
+//! ```
+//!
+//! String-like fields (String, OsString, PathBuf, `str` and `Path`) can be given a
+//! template with the `format` attribute instead of the default `{field}{index}` values.
+//! The template supports the `{i}`, `{field}` and `{struct}` placeholders for the index,
+//! the field's name and the struct's name, and `{{` or `}}` for literal braces. Templates
+//! are checked at compile time and also apply to the elements of populated collections.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Employee {
+//!     #[tlayuda(format = "user-{i}@corp.test")]
+//!     pub email: String,
+//!     #[tlayuda(format = "{struct}/{field}/{i}")]
+//!     pub badge: String,
+//!     #[tlayuda(len = 2, format = "https://corp.test/{i}")]
+//!     pub links: Vec<String>,
+//! }
+//!
+//! /* inside a test */
+//! let employee = Employee::tlayuda().with_index(5).build();
+//! assert_eq!("user-5@corp.test", employee.email);
+//! assert_eq!("Employee/badge/5", employee.badge);
+//! assert_eq!(vec!["https://corp.test/10", "https://corp.test/11"], employee.links);
+//! ```
+//!
+//! Numeric fields default to the index, which may not be a valid value and can overflow
+//! small types. The `range` attribute counts through a range and starts over at its end,
+//! `start` and `step` count from `start` (0 by default) by `step` (1 by default) and
+//! `cycle` repeats a list of values. These also apply to numeric arrays, collections and
+//! Options.
+//!
+//! Ranges count by 1 from their start, so a float range like `0.5..=2.5` gives 0.5, 1.5
+//! and 2.5. A range written with literals that has fewer than two values is rejected at
+//! compile time, and one using constants panics at runtime if it's empty. Values from
+//! `cycle` go through the same overflow policy as every other numeric value (see below).
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Invoice {
+//!     #[tlayuda(start = 1000, step = 5)]
+//!     pub id: u64,
+//!     #[tlayuda(range = 1..=100)]
+//!     pub discount_percent: u8,
+//!     #[tlayuda(cycle = [9.99, 19.99, 49.99])]
+//!     pub price: f64,
+//!     #[tlayuda(range = 0..=9)]
+//!     pub pin: [u8; 4],
+//! }
+//!
+//! /* inside a test */
+//! let invoices = Invoice::tlayuda().build_vec(101);
+//! assert_eq!(1000, invoices[0].id);
+//! assert_eq!(1005, invoices[1].id);
+//! assert_eq!(100, invoices[99].discount_percent);
+//! assert_eq!(1, invoices[100].discount_percent);
+//! assert_eq!(49.99, invoices[2].price);
+//! assert_eq!([4, 5, 6, 7], invoices[1].pin);
+//! ```
+//!
+//! Numeric values that don't fit in a field's type wrap by default, the same as an `as`
+//! conversion, so a `u8` field goes back to 0 after 255. The `overflow` attribute, on a
+//! struct or on a single field, changes this to `"saturate"` (the type's minimum or
+//! maximum) or `"panic"` (a panic naming the field and index), which keeps uniqueness
+//! tests from silently passing duplicates. It can be set back to `"wrap"` for a field.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! #[tlayuda(overflow = "saturate")]
+//! pub struct Reading {
+//!     pub level: u8,
+//!     #[tlayuda(overflow = "panic")]
+//!     pub sensor_id: u16,
+//!     #[tlayuda(overflow = "wrap")]
+//!     pub sequence: u8,
+//! }
+//!
+//! /* inside a test */
+//! let reading = Reading::tlayuda().with_index(300).build();
+//! assert_eq!(255, reading.level);
+//! assert_eq!(300, reading.sensor_id);
+//! assert_eq!(44, reading.sequence);
+//! ```
+//!
+//! Calling `with_seed(u64)` on a builder switches its dynamic defaults into seeded
+//! pseudo-random generation. Each field gets its own stream of values, derived from the
+//! seed and the field's name using a small bundled generator (`tlayuda::runtime::Rng`),
+//! and attributes like `range`, `format` and `regex` still apply. Seeded numbers always fit
+//! the field's type, and bools are no longer always `false`. The same seed reproduces the
+//! same values across runs and platforms so a failing randomized test can be replayed.
+//! Closures passed to `set_` methods, `default` and `with` expressions, and `start`/`step`
+//! or `cycle` fields still receive the object's index.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Shipment {
+//!     pub tracking_code: String,
+//!     #[tlayuda(range = 1..=50)]
+//!     pub weight_kg: u32,
+//! }
+//!
+//! /* inside a test */
+//! let shipments = Shipment::tlayuda().with_seed(1234).build_vec(10);
+//! let replayed = Shipment::tlayuda().with_seed(1234).build_vec(10);
+//!
+//! assert_ne!("tracking_code0", shipments[0].tracking_code);
+//! assert_eq!(shipments[0].tracking_code, replayed[0].tracking_code);
+//! assert!(shipments.iter().all(|s| (1..=50).contains(&s.weight_kg)));
+//! ```
+//!
+//! The `fake` attribute fills a string-like field with realistic looking values from a
+//! catalog embedded in Tlayuda, so no network access or data files are needed. The kinds
+//! are `first_name`, `last_name`, `full_name`, `email`, `phone`, `street_address`,
+//! `company`, `lorem`, `url` and `hex_color`, each generated by the function of the same
+//! name in `tlayuda::runtime::fake`. Values are picked from the index, or from the seed
+//! when `with_seed` is used.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Customer {
+//!     #[tlayuda(fake = "full_name")]
+//!     pub name: String,
+//!     #[tlayuda(fake = "email")]
+//!     pub email: String,
+//!     #[tlayuda(fake = "hex_color")]
+//!     pub favorite_color: String,
+//! }
+//!
+//! /* inside a test */
+//! let customer = Customer::tlayuda().with_index(2).build();
+//!
+//! assert_eq!(tlayuda::runtime::fake::full_name(2), customer.name);
+//! assert!(customer.email.ends_with("2@example.org"));
+//! assert_eq!(7, customer.favorite_color.len());
+//! ```
+//!
+//! The `regex` attribute generates string-like values that match a pattern, so test data
+//! follows the same format as the real validation. The pattern is checked when the macro
+//! runs and an invalid one is a compile error. Every value is a whole match, and the
+//! index decides the value so different indexes give different matches. Literals,
+//! escapes, `.`, classes like `[a-z]` and `\d`, groups, `|` and quantifiers are
+//! supported, with `*`, `+` and `{n,}` repeating at most 8 extra times. Things like
+//! `\b`, lookarounds and backreferences aren't supported.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Shipment {
+//!     #[tlayuda(regex = r"[A-Z]{3}-\d{4}")]
+//!     pub tracking_code: String,
+//!     #[tlayuda(regex = "US|GB|MX")]
+//!     pub country: String,
+//! }
+//!
+//! /* inside a test */
+//! let shipments = Shipment::tlayuda().build_vec(3);
+//!
+//! assert_eq!(8, shipments[0].tracking_code.len());
+//! assert_ne!(shipments[0].tracking_code, shipments[1].tracking_code);
+//! assert_eq!("GB", shipments[1].country);
+//! ```
+//!
+//! Calling `with_edge_cases()` on a builder makes each field start with a curated list of
+//! edge cases for its type before going back to the usual dynamic defaults. Strings go
+//! through empty and whitespace-only values, a very long string, accented, CJK, emoji and
+//! right-to-left text, and a few characters that often break escaping. Integers go through
+//! zero, one, `MIN` and `MAX`, and floats add NaN and the infinities. `Option` fields start
+//! with `None`. Running `build_vec(n)` then sweeps these boundary conditions through the
+//! code under test. Fields using `set_` functions or another value attribute are left
+//! alone. Use `#[tlayuda(edge_cases)]` to always do this for a single field. The lists
+//! come from the `tlayuda::runtime::EdgeCases` trait.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Reading {
+//!     pub label: String,
+//!     pub value: f64,
+//!     #[tlayuda(edge_cases)]
+//!     pub sensor: u8,
+//! }
+//!
+//! /* inside a test */
+//! let readings = Reading::tlayuda().with_edge_cases().build_vec(20);
+//!
+//! assert_eq!("", readings[0].label);
+//! assert!(readings[3].value.is_nan());
+//! assert_eq!(u8::MAX, readings[2].sensor);
+//! assert_eq!("label19", readings[19].label);
+//!
+//! let reading = Reading::tlayuda().build();
+//! assert_eq!("label0", reading.label);
+//! assert_eq!(0, reading.sensor);
 //! ```
 //!
 //! # Supported Types
@@ -275,9 +833,38 @@
 //! * bools
 //! * char
 //! * String, OsString
-//! * Vecs
-//! * Arrays with numeric primitives
-//! * structs composed **solely** from the above types (and that are using the Tlayuda macro)
+//! * PathBuf, Duration, SystemTime, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6
+//! * Vecs and the other standard collections (VecDeque, LinkedList, BinaryHeap, HashMap, BTreeMap, HashSet, BTreeSet)
+//! * Arrays of any of the above types (including nested arrays)
+//! * tuples of any of the above types
+//! * Options, smart pointers (Box, Rc, Arc) and cells (Cell, RefCell, Mutex, RwLock) of any of the above types
+//! * generic type parameters and any other types that implement `TlayudaDefault`
+//! * references to the above types, `str` and slices (see the `leak` attribute)
+//! * structs and tuple structs composed **solely** from the above types (and that are using the Tlayuda macro)
+//! * enums whose variants are units or carry the above types (and that are using the Tlayuda macro)
+//!
+//! `Option<T>` fields are populated with `Some` wrapping the dynamic default of `T`.
+//! This can be changed per field with the `option` attribute, which accepts `"some"`,
+//! `"none"` or `"alternate"` (`Some` on even indexes and `None` on odd indexes). The
+//! builder also gets `set_{field}_some` and `set_{field}_none` methods for Option fields.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! pub struct Account {
+//!     pub nickname: Option<String>,
+//!     #[tlayuda(option = "alternate")]
+//!     pub closed_on: Option<u64>,
+//! }
+//!
+//! /* inside a test */
+//! let account = Account::tlayuda()
+//!     .set_nickname_none()
+//!     .build();
+//!
+//! assert_eq!(None, account.nickname);
+//! assert_eq!(Some(0), account.closed_on);
+//! ```
 //!
 //! Types with full paths will have their paths ignored and behave as whatever the 
 //! last segment is. I.e., "std::ffi::OsString" will be treated as "OsString."
@@ -299,23 +886,20 @@
 //!         pub some_unsupported_type: Vec::<u32>,
 //!     }
 //!
-//! # #[test]
-//! # fn test() {
 //!     /* inside a test */
 //!
 //!     // construct a value for the unsupported type
 //!     let some_vec: Vec::<u32> = vec![1, 2, 3]; 
 //!
 //!     // ignored field now required as a parameter instead of being handled by tlayuda
-//!     let mut builder = structA::tlayuda(some_vec); 
+//!     let mut builder = StructA::tlayuda(some_vec); 
 //!     let some_1 = builder.build(); 
 //!
 //!     // value gets populated with value passed into tlayuda()
-//!     assert_eq!(100, some_1.some_unsupported_type[0]);
+//!     assert_eq!(1, some_1.some_unsupported_type[0]);
 //!
 //!     let some_2 = builder.build(); 
-//!     assert_eq!(100, some_2.some_unsupported_type[0]); // value is cloned across builds
-//! # }
+//!     assert_eq!(1, some_2.some_unsupported_type[0]); // value is cloned across builds
 //! ```
 //!
 //! # Running outside of Tests
@@ -342,14 +926,10 @@
 //! #       is_active: bool
 //! #   }
 //! # // implementation isn't important, this is just a stub for demonstration
-//! # #[test]
 //! # fn get_by_id(_id: usize) -> Person { Person::tlayuda().build() }
-//! # #[test]
-//! # fn test() {
 //! /* inside a test */
 //! let person = get_by_id(0); // some function using generated data
 //! assert_eq!("first_name0", person.first_name); // BAD
-//! # }
 //! ```
 //!
 //! While this works, this is a bad pattern because it's possible the assert
@@ -369,11 +949,6 @@
 //! #       last_name: String,
 //! #       is_active: bool
 //! #   }
-//! # // implementation isn't important, this is just a stub for demonstration
-//! # #[test]
-//! # fn get_by_id(_id: usize) -> Person { Person::tlayuda().build() }
-//! # #[test]
-//! # fn test() {
 //! // create a custom builder and use this to generate data
 //! let builder = Person::tlayuda()
 //!                     .set_first_name(|i| {
@@ -383,11 +958,38 @@
 //!                             format!("first_name{}", i)
 //!                         }
 //!                     });
+//! # // implementation isn't important, this is just a stub for demonstration
+//! # let mut builder = builder;
+//! # let mut get_by_id = |_id: usize| builder.build();
 //! 
 //! /* inside a test */
 //! let person = get_by_id(0); // some function using generated data
 //! assert_eq!("Michael", person.first_name); // GOOD
-//! # }
+//! ```
+//!
+//! To catch tests like this, add `#[tlayuda(opaque)]` to a struct or enum, or enable the
+//! "opaque_defaults" feature to turn it on for every builder. Opaque builders start out
+//! seeded as if `with_seed` had been called, so any field that isn't set gets an
+//! unpredictable value and a test depending on a default fails. The seed is picked at
+//! random once per thread and printed to stderr for every later panic on that thread, even
+//! one expected by `#[should_panic]` or caught with `catch_unwind`. Set the `TLAYUDA_SEED`
+//! environment variable to that seed to reproduce the run.
+//!
+//! ```
+//! # use crate::tlayuda::*;
+//! #[derive(Tlayuda)]
+//! #[tlayuda(opaque)]
+//! pub struct Person {
+//!     id: u32,
+//!     first_name: String,
+//! }
+//!
+//! /* inside a test */
+//! let person = Person::tlayuda().build();
+//! assert_ne!("first_name0", person.first_name);
+//!
+//! let person = Person::tlayuda().set_first_name(|_| "Michael".to_string()).build();
+//! assert_eq!("Michael", person.first_name);
 //! ```
 //!
 //! # Example Output
@@ -396,11 +998,13 @@
 //!
 //! 
 //! ```
+//! # // the derive lives in its own module so the expansion below can be compiled next to it
+//! # mod derived {
 //! use crate::tlayuda::*;
 //!
 //! // given this struct
 //! #[derive(Tlayuda)]
-//! struct Person {
+//! pub struct Person {
 //!     id: u32,
 //!     first_name: String,
 //!     last_name: String,
@@ -408,6 +1012,14 @@
 //!     #[tlayuda_ignore]
 //!     friends: Vec::<u32>,
 //! }
+//! # }
+//! # struct Person {
+//! #     id: u32,
+//! #     first_name: String,
+//! #     last_name: String,
+//! #     is_active: bool,
+//! #     friends: Vec::<u32>,
+//! # }
 //!
 //! // Tlayuda generates the following
 //!
@@ -461,8 +1073,9 @@
 //!     }
 //!
 //!     fn take_index(&mut self) -> usize {
-//!         self.index = self.index + 1;
-//!         self.index - 1
+//!         let index = self.index;
+//!         self.index = self.index.wrapping_add(1);
+//!         index
 //!     }
 //!
 //!     pub fn build(&mut self) -> Person {
@@ -489,279 +1102,7 @@
 //! ```
 //!
 
-use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
-use syn::{parse_macro_input, ItemStruct, Meta, Type};
-
-
-/// A derive macro that generates a test data builder for a struct
-#[proc_macro_derive(Tlayuda, attributes(tlayuda_ignore))]
-pub fn entry_point(input: TokenStream) -> TokenStream {
-    let source_struct = parse_macro_input!(input as ItemStruct);
-    let source_struct_name = source_struct.ident.clone();
-    let fields = get_fields(source_struct);
-    let inner_builder_name = quote::format_ident!("Tlayuda{}Builder", source_struct_name);
-
-    let OutputTokenPartials {
-        field_declarations,
-        field_builder_intializers,
-        field_setter_functions,
-    } = generate_output_tokens(&fields);
-
-    let builder_parameters = fields
-        .iter()
-        .filter(|f| f.is_ignored)
-        .map(|f| {
-            let identifier = &f.identifier;
-            let field_type = &f.field_type;
-
-            quote! { #identifier: #field_type }
-        })
-        .collect::<Vec<_>>();
-
-    let (ignored_fields, fields): (Vec<_>, Vec<_>) = fields.iter().partition(|f| f.is_ignored);
-
-    // Ignored fields will be manually populated by the user with a clonable
-    // instance passed into the initial .tlayuda() call. The following
-    // is intended to create those parameters.
-    let inner_builder_constructor_parameters = ignored_fields.iter()
-                                                             .map(|f| {
-                                                                 let i = &f.identifier;
-                                                                 quote! { #i }
-                                                             });
-    let ignored_fields = ignored_fields.iter()
-                                       .map(|f| {
-                                           let inner_identifier = quote::format_ident!("inner_{}", f.identifier);
-                                           let identifier = &f.identifier;
-                                           quote! { #identifier: self.#inner_identifier.clone(), }
-                                       });
-
-    let fields = fields.iter()
-                       .map(|f| {
-                           let inner_identifier = quote::format_ident!("inner_{}", f.identifier);
-                           let identifier = &f.identifier;
-                           quote! { #identifier: self.#inner_identifier.as_mut()(i), }
-                       });
-
-    let output = quote! {
-        #[cfg(any(test, feature="allow_outside_tests"))]
-        pub struct #inner_builder_name {
-            index: usize,
-            #(#field_declarations),*
-        }
-
-        #[cfg(any(test, feature="allow_outside_tests"))]
-        impl #inner_builder_name {
-            pub fn new(#(#builder_parameters),*) -> #inner_builder_name {
-                #inner_builder_name {
-                    index: 0,
-                    #(#field_builder_intializers),*
-                }
-            }
-
-            #(#field_setter_functions)*
-
-            pub fn with_index(mut self, index: usize) -> Self {
-                self.index = index;
-                self
-            }
-
-            fn take_index(&mut self) -> usize {
-                self.index = self.index + 1;
-                self.index - 1
-            }
-
-            pub fn build(&mut self) -> #source_struct_name {
-                let i = self.take_index();
-                #source_struct_name {
-                    #(#ignored_fields)*
-                    #(#fields)*
-                }
-            }
-
-            pub fn build_vec(&mut self, count: usize) -> Vec::<#source_struct_name> {
-                std::iter::repeat_with(|| self.build()).take(count).collect()
-            }
-        }
-
-        #[cfg(any(test, feature="allow_outside_tests"))]
-        impl #source_struct_name {
-            pub fn tlayuda(#(#builder_parameters),*) -> #inner_builder_name {
-                #inner_builder_name::new(#(#inner_builder_constructor_parameters),* )
-            }
-        }
-    };
-
-    TokenStream::from(output)
-}
-
-#[derive(Debug)]
-struct FieldInfo {
-    identifier: proc_macro2::Ident,
-    field_type: syn::Type,
-    is_ignored: bool,
-}
-
-fn get_fields(item_struct: ItemStruct) -> Vec<FieldInfo> {
-    item_struct
-        .fields
-        .iter()
-        .filter(|x| x.ident.is_some())
-        .map(|x| FieldInfo {
-            identifier: x.ident.as_ref().unwrap().clone(),
-            field_type: x.ty.clone(),
-            is_ignored: x.attrs.iter().any(|attribute| {
-                if let Ok(meta) = attribute.parse_meta() {
-                    match meta {
-                        Meta::Path(path) => path.is_ident("tlayuda_ignore".into()),
-                        _ => false,
-                    }
-                } else {
-                    false
-                }
-            }),
-        })
-        .collect()
-}
-
-struct OutputTokenPartials {
-    field_setter_functions: Vec<proc_macro2::TokenStream>,
-    field_builder_intializers: Vec<proc_macro2::TokenStream>,
-    field_declarations: Vec<proc_macro2::TokenStream>,
-}
-
-fn generate_output_tokens(fields: &Vec<FieldInfo>) -> OutputTokenPartials {
-    let field_setter_functions = fields
-        .iter()
-        .filter(|f| !f.is_ignored)
-        .map(|field| {
-            let set_func_name = quote::format_ident!("set_{}", field.identifier);
-            let identifier = quote::format_ident!("inner_{}", field.identifier);
-            let field_type = &field.field_type;
-
-            quote! {
-                pub fn #set_func_name<F: 'static>(mut self, f: F) -> Self where
-                    F: Fn(usize) -> #field_type {
-                        self.#identifier = Box::new(f);
-                        self
-                }
-            }
-        })
-        .collect();
-
-    let field_builder_intializers = fields
-        .iter()
-        .map(|field| {
-            let inner_identifier = quote::format_ident!("inner_{}", field.identifier);
-            let identifier = &field.identifier;
-
-            if field.is_ignored {
-                let value = &field.identifier;
-                quote! { #inner_identifier: #value }
-            } else {
-                let field_type = parse_field_type(&field.field_type);
-
-                let f =
-                match field_type {
-                    FieldType::Basic(field_type, full_field_type) => {
-                        match field_type.to_string().as_str() {
-                            "String" => quote! { |i| format!("{}{}", stringify!(#identifier), i).into() },
-                            "OsString" => quote! { |i| format!("{}{}", stringify!(#identifier), i).into() },
-                            "char" => quote! { |i| std::char::from_digit(i as u32, 10).unwrap_or('a') },
-                            "bool" => quote! { |i| false },
-                            "i8" | "i16" | "i32" | "u8" | "u16" | "u32" | "i64" | "i128" | "isize"
-                            | "u64" | "u128" | "usize" | "f32" | "f64" => {
-                                quote! { |i| i as #full_field_type }
-                            },
-                            "Vec" => quote! { |i| Vec::new() },
-                            _ => {
-                                // attempt to call a builder that may be on this type
-                                // this will end up causing a compile error if the type doesn't have
-                                // the #[derive(Tlayuda)] macro.
-                                // TODO: Need to figure out a way to communicate this better in the compiler
-                                quote! { |i| #full_field_type::tlayuda().with_index(i).build() }
-                            }
-                        }
-                    },
-                    FieldType::Array(field_type, full_field_type, length) => {
-                        match field_type.to_string().as_str() {
-                            "i8" | "i16" | "i32" | "u8" | "u16" | "u32" | "i64" | "i128" | "isize"
-                            | "u64" | "u128" | "usize" | "f32" | "f64" => {
-                                quote! { |i| [i as #full_field_type; #length] }
-                            },
-                            _ => panic!("Type {:?} not yet supported for arrays", field_type)
-                        }
-                    }
-                };
-
-                quote! { #inner_identifier: Box::new(#f) }
-            }
-        })
-        .collect();
-
-    let field_declarations = fields
-        .iter()
-        .map(
-            |FieldInfo {
-                 identifier,
-                 field_type,
-                 is_ignored,
-             }| {
-                let identifier = quote::format_ident!("inner_{}", identifier);
-                if *is_ignored {
-                    quote! { #identifier: #field_type }
-                } else {
-                    quote! {
-                        #identifier: Box<dyn FnMut(usize) -> #field_type>
-                    }
-                }
-            },
-        )
-        .collect();
-
-    OutputTokenPartials {
-        field_declarations,
-        field_builder_intializers,
-        field_setter_functions,
-    }
-}
-
-enum FieldType {
-    Basic(syn::Ident, proc_macro2::TokenStream),
-    Array(syn::Ident, proc_macro2::TokenStream, usize),
-}
+pub mod runtime;
 
-fn parse_field_type(field_type: &syn::Type) -> FieldType {
-    match field_type {
-        Type::Path(type_path) => match type_path.path.get_ident() {
-            Some(ident) => FieldType::Basic(ident.clone(), ident.into_token_stream()),
-            None => (
-                FieldType::Basic(type_path.path.segments.last().unwrap().ident.clone(),
-                                 type_path.into_token_stream())
-            ),
-        },
-        Type::Array(type_array) => {
-            match parse_field_type(&type_array.elem) {
-                FieldType::Basic(i, ts) => {
-                    match &type_array.len {
-                        syn::Expr::Lit(expr) => {
-                            match &expr.lit {
-                                syn::Lit::Int(number) => {
-                                    match number.base10_parse::<usize>() {
-                                        Ok(parsed_number) => FieldType::Array(i, ts, parsed_number),
-                                        _ => panic!("Number literal in array was invalid: {:?}", number)
-                                    }
-                                },
-                                _ => todo!("Array length literal {:?} not yet supported", expr)
-                            }
-                            
-                        },
-                        _ => todo!("Array length expression {:?} not yet supported", type_array.len)
-                    }
-                },
-                _ => todo!("Nested arrays not yet supported")
-            }
-        },
-        _ => todo!("Type {:?} not supported", field_type),
-    }
-}
+pub use tlayuda_derive::Tlayuda;
+pub use runtime::{Tlayuda, TlayudaBuilder};
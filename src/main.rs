@@ -1,5 +1,6 @@
 use tlayuda::*;
 
+#[allow(dead_code)]
 #[derive(Debug, Tlayuda)]
 pub struct TemplateExample {
     first_name: String,
@@ -16,25 +17,31 @@ pub struct ExampleBuilder {
     first_name: Box<dyn FnMut(usize) -> String>,
     last_name: Box<dyn FnMut(usize) -> String>,
 }
+impl Default for ExampleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ExampleBuilder {
     pub fn new() -> ExampleBuilder {
         ExampleBuilder {
-            first_name: Box::new(|x| format!("first_name{}", x).into()),
-            last_name: Box::new(|x| format!("last_name{}", x).into()),
+            first_name: Box::new(|x| format!("first_name{}", x)),
+            last_name: Box::new(|x| format!("last_name{}", x)),
         }
     }
 
-    pub fn set_first_name<F: 'static>(mut self, f: F) -> Self
+    pub fn set_first_name<F>(mut self, f: F) -> Self
     where
-        F: Fn(usize) -> String,
+        F: Fn(usize) -> String + 'static,
     {
         self.first_name = Box::new(f);
         self
     }
 
-    pub fn _set_last_name<F: 'static>(mut self, f: F) -> Self
+    pub fn _set_last_name<F>(mut self, f: F) -> Self
     where
-        F: Fn(usize) -> String,
+        F: Fn(usize) -> String + 'static,
     {
         self.last_name = Box::new(f);
         self
@@ -54,13 +61,13 @@ impl ExampleBuilder {
 fn main() {
     // this is a scratch pad example to model macro output after
     let mut example = TemplateExample::build();
-    example = example.set_first_name(|i| format!("Example{}", i).into());
+    example = example.set_first_name(|i| format!("Example{}", i));
     println!("{:?}", example.build());
 
     // this is to test enabling allow_outside_tests feature
     /*
         let mut example = TemplateExample::tlayuda();
-        example = example.set_first_name(|i| format!("Example{}", i).into());
+        example = example.set_first_name(|i| format!("Example{}", i));
         println!("{:?}", example.build());
     */
 }
@@ -0,0 +1,689 @@
+//! Shared code that the builders generated by `#[derive(Tlayuda)]` call into. Items
+//! hidden from the documentation are only meant for the generated code and may change in
+//! any release.
+//!
+//! Fields whose type the derive doesn't know how to build fall back to
+//! [`TlayudaDefault`]. Implement it for your own types, or for a foreign type through
+//! a local newtype, to give them dynamic defaults.
+//!
+//! ```
+//! use tlayuda::runtime::TlayudaDefault;
+//!
+//! pub struct Sku(String);
+//!
+//! impl TlayudaDefault for Sku {
+//!     fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+//!         Sku(format!("SKU-{:04}", index))
+//!     }
+//! }
+//!
+//! assert_eq!("SKU-0012", Sku::tlayuda_default(12, "sku").0);
+//! ```
+
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::convert::TryFrom;
+use std::env;
+use std::ffi::OsString;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::panic;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::{Arc, Once, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub mod fake;
+
+/// A "dynamic default" for a type, built from the index of the object being built and
+/// the name of the field the value is for.
+///
+/// Every type that derives `Tlayuda` without ignored fields implements this trait by
+/// building itself with its builder at the given index.
+#[diagnostic::on_unimplemented(
+    message = "Tlayuda doesn't know how to build a `{Self}`",
+    label = "add #[derive(Tlayuda)] to this type or implement TlayudaDefault for it"
+)]
+pub trait TlayudaDefault {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self;
+}
+
+/// Implemented by every generated `Tlayuda{}Builder` so that test helpers can be
+/// written once for any builder.
+///
+/// ```
+/// use tlayuda::TlayudaBuilder;
+///
+/// fn build_three<B: TlayudaBuilder>(mut builder: B) -> Vec<B::Output> {
+///     builder.build_vec(3)
+/// }
+/// ```
+pub trait TlayudaBuilder: Sized {
+    /// The type the builder builds
+    type Output;
+
+    /// Sets the index the next object is built with
+    fn with_index(self, index: usize) -> Self;
+
+    /// Switches the builder's dynamic defaults into seeded pseudo-random generation
+    fn with_seed(self, seed: u64) -> Self;
+
+    /// Makes the builder start each field that has [`EdgeCases`] with them
+    fn with_edge_cases(self) -> Self;
+
+    /// Builds an object using the current index, then increments the index
+    fn build(&mut self) -> Self::Output;
+
+    /// Builds `count` objects, incrementing the index after each one
+    fn build_vec(&mut self, count: usize) -> Vec<Self::Output> {
+        std::iter::repeat_with(|| self.build()).take(count).collect()
+    }
+}
+
+/// Implemented by every type deriving `Tlayuda` that has no ignored fields, since
+/// those need to be passed into the builder's constructor.
+///
+/// ```
+/// use tlayuda::{Tlayuda, TlayudaBuilder};
+///
+/// fn first<T: Tlayuda>() -> T {
+///     T::tlayuda().build()
+/// }
+/// ```
+pub trait Tlayuda: Sized {
+    type Builder: TlayudaBuilder<Output = Self>;
+
+    /// Returns a new builder starting at index 0
+    fn tlayuda() -> Self::Builder;
+}
+
+macro_rules! impl_numeric {
+    ($($numeric:ty),*) => {
+        $(
+            impl TlayudaDefault for $numeric {
+                fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+                    index as $numeric
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+macro_rules! impl_named {
+    ($($named:ty),*) => {
+        $(
+            impl TlayudaDefault for $named {
+                fn tlayuda_default(index: usize, field_name: &str) -> Self {
+                    format!("{}{}", field_name, index).into()
+                }
+            }
+        )*
+    };
+}
+
+impl_named!(String, OsString, PathBuf);
+
+impl TlayudaDefault for bool {
+    fn tlayuda_default(_index: usize, _field_name: &str) -> Self {
+        false
+    }
+}
+
+impl TlayudaDefault for char {
+    fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+        std::char::from_digit(index as u32, 10).unwrap_or('a')
+    }
+}
+
+impl TlayudaDefault for Duration {
+    fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+        Duration::from_secs(index as u64)
+    }
+}
+
+impl TlayudaDefault for SystemTime {
+    fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+        UNIX_EPOCH + Duration::from_secs(index as u64)
+    }
+}
+
+// addresses are made from the index's bytes, i.e. index 258 is 0.0.1.2
+impl TlayudaDefault for Ipv4Addr {
+    fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+        Ipv4Addr::from(index as u32)
+    }
+}
+
+impl TlayudaDefault for Ipv6Addr {
+    fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+        Ipv6Addr::from(index as u128)
+    }
+}
+
+impl TlayudaDefault for IpAddr {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self {
+        IpAddr::V4(Ipv4Addr::tlayuda_default(index, field_name))
+    }
+}
+
+impl TlayudaDefault for SocketAddrV4 {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self {
+        SocketAddrV4::new(Ipv4Addr::tlayuda_default(index, field_name), index as u16)
+    }
+}
+
+impl TlayudaDefault for SocketAddrV6 {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self {
+        SocketAddrV6::new(Ipv6Addr::tlayuda_default(index, field_name), index as u16, 0, 0)
+    }
+}
+
+impl TlayudaDefault for SocketAddr {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self {
+        SocketAddr::V4(SocketAddrV4::tlayuda_default(index, field_name))
+    }
+}
+
+impl<T: TlayudaDefault> TlayudaDefault for Option<T> {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self {
+        Some(T::tlayuda_default(index, field_name))
+    }
+}
+
+impl<T: TlayudaDefault> TlayudaDefault for Box<T> {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self {
+        Box::new(T::tlayuda_default(index, field_name))
+    }
+}
+
+impl<T: TlayudaDefault> TlayudaDefault for Rc<T> {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self {
+        Rc::new(T::tlayuda_default(index, field_name))
+    }
+}
+
+impl<T: TlayudaDefault> TlayudaDefault for Arc<T> {
+    fn tlayuda_default(index: usize, field_name: &str) -> Self {
+        Arc::new(T::tlayuda_default(index, field_name))
+    }
+}
+
+impl<T> TlayudaDefault for Vec<T> {
+    fn tlayuda_default(_index: usize, _field_name: &str) -> Self {
+        Vec::new()
+    }
+}
+
+/// A function that generates a field's values, used by the `with` attribute. It's
+/// implemented for functions taking the index, `fn(usize) -> T`, and functions taking
+/// the index and the field's name, `fn(usize, &str) -> T`.
+///
+/// `Signature` only tells the two implementations apart and is inferred.
+pub trait Generator<Signature, T> {
+    fn generate(&self, index: usize, field_name: &str) -> T;
+}
+
+/// Marks a [`Generator`] that takes the index
+pub struct WithIndex;
+
+/// Marks a [`Generator`] that takes the index and the field's name
+pub struct WithIndexAndName;
+
+impl<F, T> Generator<WithIndex, T> for F
+where
+    F: Fn(usize) -> T,
+{
+    fn generate(&self, index: usize, _field_name: &str) -> T {
+        self(index)
+    }
+}
+
+impl<F, T> Generator<WithIndexAndName, T> for F
+where
+    F: Fn(usize, &str) -> T,
+{
+    fn generate(&self, index: usize, field_name: &str) -> T {
+        self(index, field_name)
+    }
+}
+
+/// Values of a type that tend to break the code handling it, like empty strings, NaN or
+/// `MAX`. A builder's edge case mode and the `edge_cases` attribute build these first,
+/// one per index, before going back to the usual dynamic defaults.
+///
+/// ```
+/// use tlayuda::runtime::EdgeCases;
+///
+/// assert_eq!(Some(String::new()), String::edge_case(0));
+/// assert!(f64::edge_case(3).is_some_and(f64::is_nan));
+/// assert_eq!(None, bool::edge_case(2));
+/// ```
+pub trait EdgeCases: Sized {
+    /// The edge case at `index`, or `None` once they run out
+    fn edge_case(index: usize) -> Option<Self>;
+}
+
+macro_rules! impl_signed_edge_cases {
+    ($($signed:ty),*) => {
+        $(
+            impl EdgeCases for $signed {
+                fn edge_case(index: usize) -> Option<Self> {
+                    [0, 1, -1, <$signed>::MIN, <$signed>::MAX].get(index).copied()
+                }
+            }
+        )*
+    };
+}
+
+impl_signed_edge_cases!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_unsigned_edge_cases {
+    ($($unsigned:ty),*) => {
+        $(
+            impl EdgeCases for $unsigned {
+                fn edge_case(index: usize) -> Option<Self> {
+                    [0, 1, <$unsigned>::MAX].get(index).copied()
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned_edge_cases!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_float_edge_cases {
+    ($($float:ty),*) => {
+        $(
+            impl EdgeCases for $float {
+                fn edge_case(index: usize) -> Option<Self> {
+                    [
+                        0.0,
+                        -0.0,
+                        -1.0,
+                        <$float>::NAN,
+                        <$float>::INFINITY,
+                        <$float>::NEG_INFINITY,
+                        <$float>::MIN,
+                        <$float>::MAX,
+                        <$float>::MIN_POSITIVE,
+                        <$float>::EPSILON,
+                    ]
+                    .get(index)
+                    .copied()
+                }
+            }
+        )*
+    };
+}
+
+impl_float_edge_cases!(f32, f64);
+
+impl EdgeCases for &str {
+    fn edge_case(index: usize) -> Option<Self> {
+        static LONG: OnceLock<String> = OnceLock::new();
+
+        [
+            "",
+            " ",
+            "\t\r\n",
+            "  padded  ",
+            LONG.get_or_init(|| "long ".repeat(2_000)),
+            "Zoë Ångström-Łukasz",
+            "日本語のテキスト",
+            "👩‍👩‍👧‍👦🎉👍🏽",
+            "مرحبا بالعالم",
+            "e\u{301}\u{200B}\u{202E}reversed",
+            "\0",
+            "line one\nline two",
+            "'\"<script>&amp;; DROP TABLE --",
+        ]
+        .get(index)
+        .copied()
+    }
+}
+
+macro_rules! impl_string_edge_cases {
+    ($($string:ty),*) => {
+        $(
+            impl EdgeCases for $string {
+                fn edge_case(index: usize) -> Option<Self> {
+                    <&str>::edge_case(index).map(Into::into)
+                }
+            }
+        )*
+    };
+}
+
+impl_string_edge_cases!(String, OsString, PathBuf);
+
+impl EdgeCases for char {
+    fn edge_case(index: usize) -> Option<Self> {
+        ['\0', ' ', '\n', 'ë', '😀', '\u{200F}', std::char::MAX].get(index).copied()
+    }
+}
+
+impl EdgeCases for bool {
+    fn edge_case(index: usize) -> Option<Self> {
+        [false, true].get(index).copied()
+    }
+}
+
+impl<T: EdgeCases> EdgeCases for Option<T> {
+    fn edge_case(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(None),
+            _ => T::edge_case(index - 1).map(Some),
+        }
+    }
+}
+
+/// A small, dependency free pseudo-random number generator (SplitMix64). Its output
+/// only depends on the seed, so the same seed gives the same values on every platform.
+///
+/// ```
+/// use tlayuda::runtime::Rng;
+///
+/// let mut rng = Rng::new(42);
+/// let first = rng.next_u64();
+/// assert_eq!(first, Rng::new(42).next_u64());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// A generator whose values are independent from the streams of other field names
+    pub fn for_field(seed: u64, field_name: &str) -> Rng {
+        // FNV-1a keeps the stream stable regardless of the platform's hasher
+        let hash = field_name.bytes().fold(0xCBF2_9CE4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+        });
+        Rng::new(seed ^ Rng::mix(hash))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(Rng::GAMMA);
+        Rng::mix(self.state)
+    }
+
+    /// The value `next_u64` would return after `position` calls, without generating the
+    /// values before it
+    pub fn nth_u64(&self, position: u64) -> u64 {
+        Rng::mix(self.state.wrapping_add(Rng::GAMMA.wrapping_mul(position.wrapping_add(1))))
+    }
+
+    fn mix(value: u64) -> u64 {
+        let value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        let value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+}
+
+/// Settings shared between a builder and the generators of its dynamic defaults
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct Defaults {
+    seed: Rc<Cell<Option<u64>>>,
+    edge_cases: Rc<Cell<bool>>,
+}
+
+impl Default for Defaults {
+    fn default() -> Self {
+        Defaults::new(false)
+    }
+}
+
+impl Defaults {
+    /// Opaque defaults start out seeded with [`opaque_seed`]. They're always on when the
+    /// `opaque_defaults` feature is enabled.
+    pub fn new(opaque: bool) -> Defaults {
+        let seed = if opaque || cfg!(feature = "opaque_defaults") {
+            Some(opaque_seed())
+        } else {
+            None
+        };
+
+        Defaults {
+            seed: Rc::new(Cell::new(seed)),
+            edge_cases: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /// Makes the generators of fields with [`EdgeCases`] start with them
+    pub fn set_edge_cases(&self) {
+        self.edge_cases.set(true);
+    }
+
+    /// The edge case for the object at `index`, when the builder's edge case mode is on or
+    /// the field is marked with `edge_cases`. `None` once a type's edge cases run out.
+    pub fn edge_case<T: EdgeCases>(&self, index: usize, always: bool) -> Option<T> {
+        if always || self.edge_cases.get() {
+            T::edge_case(index)
+        } else {
+            None
+        }
+    }
+
+    /// Switches the generators into seeded pseudo-random generation
+    pub fn set_seed(&self, seed: u64) {
+        self.seed.set(Some(seed));
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed.get()
+    }
+
+    /// The index a generator for `field_name` uses when building the object at `index`.
+    /// This is the index itself unless a seed is set, in which case it's a value from the
+    /// field's own stream. Seeded values are kept below `limit`, which is how many indexes
+    /// the field's numeric types fit, and to 32 bits so they're the same on every platform.
+    pub fn index(&self, index: usize, field_name: &str, limit: u64) -> usize {
+        match self.seed.get() {
+            Some(seed) => {
+                let limit = limit.clamp(1, 1 << 32);
+                (Rng::for_field(seed, field_name).nth_u64(index as u64) % limit) as usize
+            }
+            None => index,
+        }
+    }
+}
+
+/// Splits an index into the choices made while generating a value, used by the `regex`
+/// attribute. Each choice takes the next digit of the index, counting in the number of
+/// options, and the first option once its digits run out. Different indexes give different
+/// values while the pattern has room for them. Choices beyond what any index can reach
+/// are pseudo-random.
+///
+/// ```
+/// use tlayuda::runtime::Choices;
+///
+/// let mut choices = Choices::new(5);
+/// assert_eq!(2, choices.choose(3));
+/// assert_eq!('b', choices.char_in(&[('a', 'z')]));
+/// ```
+#[doc(hidden)]
+pub struct Choices {
+    remaining: usize,
+    // how many indexes the choices so far can tell apart, `None` once it's more than any index
+    capacity: Option<usize>,
+    rng: Rng,
+}
+
+impl Choices {
+    pub fn new(index: usize) -> Choices {
+        Choices {
+            remaining: index,
+            capacity: Some(1),
+            rng: Rng::new(index as u64),
+        }
+    }
+
+    /// Picks one of `count` options
+    pub fn choose(&mut self, count: usize) -> usize {
+        if self.capacity.is_none() {
+            return (self.rng.next_u64() % count as u64) as usize;
+        }
+
+        self.capacity = self.capacity.and_then(|capacity| capacity.checked_mul(count));
+        let choice = self.remaining % count;
+        self.remaining /= count;
+        choice
+    }
+
+    /// Picks a character from inclusive ranges of characters
+    pub fn char_in(&mut self, ranges: &[(char, char)]) -> char {
+        let size = |(start, end): &(char, char)| *end as usize - *start as usize + 1;
+        let mut choice = self.choose(ranges.iter().map(size).sum());
+
+        for range in ranges {
+            if choice < size(range) {
+                return std::char::from_u32(range.0 as u32 + choice as u32).unwrap_or(range.0);
+            }
+            choice -= size(range);
+        }
+
+        ranges[0].0
+    }
+}
+
+thread_local! {
+    static OPAQUE_SEED: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// The seed used by every builder with opaque defaults on the current thread. It's read
+/// from the `TLAYUDA_SEED` environment variable when set, otherwise it's picked at random
+/// the first time it's needed. Once it's picked, every panic on the thread prints it to
+/// stderr so the run can be reproduced, including panics that are expected by
+/// `#[should_panic]` or caught with `catch_unwind`.
+pub fn opaque_seed() -> u64 {
+    OPAQUE_SEED.with(|opaque_seed| {
+        if let Some(seed) = opaque_seed.get() {
+            return seed;
+        }
+
+        let seed = match env::var("TLAYUDA_SEED") {
+            Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+                panic!("Tlayuda can't use TLAYUDA_SEED `{}`, it must be a u64", value)
+            }),
+            Err(_) => random_seed(),
+        };
+        opaque_seed.set(Some(seed));
+        report_opaque_seed_on_panic();
+
+        seed
+    })
+}
+
+fn random_seed() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    hasher.finish()
+}
+
+fn report_opaque_seed_on_panic() {
+    static REPORTER: Once = Once::new();
+
+    REPORTER.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            previous_hook(info);
+            if let Ok(Some(seed)) = OPAQUE_SEED.try_with(Cell::get) {
+                eprintln!(
+                    "tlayuda: opaque defaults used seed {0}, run with TLAYUDA_SEED={0} to reproduce",
+                    seed
+                );
+            }
+        }));
+    });
+}
+
+/// Passes a generator through unchanged, giving a closure's parameter its `usize` type
+#[doc(hidden)]
+pub fn generator<T, F: Fn(usize) -> T>(f: F) -> F {
+    f
+}
+
+/// What happens when a numeric value doesn't fit in a field's type, set with the
+/// `overflow` attribute
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Keeps the value's lowest bits, like an `as` conversion
+    Wrap,
+    /// Uses the type's minimum or maximum value
+    Saturate,
+    /// Panics with a message naming the field and index
+    Panic,
+}
+
+/// Integer types that numeric values are converted into
+#[doc(hidden)]
+pub trait Integer: Sized {
+    /// Converts a value calculated for `field_name` at `index` into this type
+    fn from_i128(value: i128, overflow: Overflow, field_name: &str, index: usize) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl Integer for $integer {
+                fn from_i128(value: i128, overflow: Overflow, field_name: &str, index: usize) -> Self {
+                    match (<$integer>::try_from(value), overflow) {
+                        (Ok(value), _) => value,
+                        (Err(_), Overflow::Wrap) => value as $integer,
+                        (Err(_), Overflow::Saturate) if value < 0 => <$integer>::MIN,
+                        (Err(_), Overflow::Saturate) => <$integer>::MAX,
+                        (Err(_), Overflow::Panic) => panic!(
+                            "Tlayuda can't fit {} into `{}` for field `{}` at index {}",
+                            value,
+                            stringify!($integer),
+                            field_name,
+                            index
+                        ),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// The position of `index` within a range of `count` values, starting over once the
+/// end of the range is reached. Used by the `range` attribute.
+///
+/// # Panics
+/// If the range is empty
+#[doc(hidden)]
+pub fn cycle_position(index: usize, count: i128, field_name: &str) -> i128 {
+    if count <= 0 {
+        panic!("Tlayuda can't generate `{}` from an empty range", field_name);
+    }
+
+    (index as i128).rem_euclid(count)
+}
+
+/// The index used for the element at `position` within a collection or array of `len`
+/// elements, derived from the index of the object being built so that values stay
+/// unique across every built object.
+///
+/// ```
+/// // the second object's three elements follow the first object's
+/// assert_eq!(3, tlayuda::runtime::element_index(1, 3, 0));
+/// assert_eq!(5, tlayuda::runtime::element_index(1, 3, 2));
+/// ```
+#[doc(hidden)]
+pub fn element_index(index: usize, len: usize, position: usize) -> usize {
+    index.wrapping_mul(len).wrapping_add(position)
+}
@@ -0,0 +1,172 @@
+//! An offline catalog of realistic looking values, used by the `fake` attribute. Each
+//! kind the attribute accepts is the name of one of these functions.
+//!
+//! Every generator picks its value from the index alone, so the same index always
+//! gives the same value. Names keep the apostrophes and hyphens real names have, and
+//! the domains are ones reserved for documentation and testing.
+//!
+//! ```
+//! use tlayuda::runtime::fake;
+//!
+//! assert_eq!(fake::email(3), fake::email(3));
+//! assert!(fake::hex_color(3).starts_with('#'));
+//! ```
+
+use super::Rng;
+
+const FIRST_NAMES: &[&str] = &[
+    "Olivia", "Liam", "Emma", "Noah", "Amelia", "Oliver", "Sophia", "Elijah", "Mia", "Mateo",
+    "Isabella", "Lucas", "Ava", "Levi", "Aaliyah", "Kai", "Zoe", "Ezra", "Priya", "Arjun",
+    "Mei", "Hiroshi", "Fatima", "Omar", "Ngozi", "Kwame", "Ana", "Diego", "Ingrid", "Lars",
+    "Chiara", "Luca", "Yusuf", "Leila", "Siobhan", "Declan", "Anne-Marie", "Jean-Luc",
+    "Bartholomew", "Maximiliana",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Smith", "Johnson", "Williams", "Brown", "Garcia", "Miller", "Davis", "Rodriguez",
+    "Martinez", "Hernandez", "Lopez", "Wilson", "Anderson", "Thomas", "Taylor", "Moore",
+    "Nguyen", "Kim", "Patel", "Singh", "Chen", "Wang", "Tanaka", "Sato", "Okafor",
+    "Mensah", "Kowalski", "Novak", "Rossi", "Bianchi", "Muller", "Schmidt", "Larsen",
+    "Haddad", "O'Brien", "O'Connor", "McAllister", "Smith-Jones", "Van der Berg",
+    "Featherstonehaugh",
+];
+
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.net", "example.org"];
+
+const AREA_CODES: &[&str] = &["212", "303", "312", "415", "503", "617", "702", "808", "919"];
+
+const STREET_NAMES: &[&str] = &[
+    "Main", "Oak", "Pine", "Maple", "Cedar", "Elm", "Washington", "Lake", "Hill", "Park",
+    "River", "Sunset", "Highland", "Church", "Mill", "Willow", "Meadow", "Forest",
+];
+
+const STREET_SUFFIXES: &[&str] = &[
+    "Street", "Avenue", "Road", "Lane", "Drive", "Court", "Boulevard", "Way", "Place",
+];
+
+const COMPANY_WORDS: &[&str] = &[
+    "Acme", "Globex", "Initech", "Umbrella", "Stark", "Wayne", "Cyberdyne", "Soylent",
+    "Hooli", "Vandelay", "Aperture", "Tyrell", "Wonka", "Gringotts", "Monarch", "Oscorp",
+];
+
+const COMPANY_INDUSTRIES: &[&str] = &[
+    "Logistics", "Systems", "Foods", "Labs", "Holdings", "Industries", "Analytics",
+    "Consulting", "Media", "Robotics", "Textiles", "Energy",
+];
+
+const COMPANY_SUFFIXES: &[&str] = &["Inc.", "LLC", "Ltd.", "GmbH", "& Co.", "Group"];
+
+const LOREM_WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed",
+    "do", "eiusmod", "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna",
+    "aliqua", "enim", "ad", "minim", "veniam", "quis", "nostrud", "exercitation",
+    "ullamco", "laboris", "nisi", "aliquip", "ex", "ea", "commodo", "consequat", "duis",
+    "aute", "irure", "in", "reprehenderit", "voluptate", "velit", "esse", "cillum",
+    "fugiat", "nulla", "pariatur", "excepteur", "sint", "occaecat", "cupidatat",
+];
+
+const URL_PATHS: &[&str] = &[
+    "about", "products", "blog", "docs", "pricing", "careers", "support", "news",
+];
+
+// picks an element so that consecutive indexes walk through the whole list, while
+// `cycle` spreads combinations of lists across the index
+fn pick(values: &[&'static str], index: usize, cycle: usize) -> &'static str {
+    values[(index / cycle) % values.len()]
+}
+
+// keeps only the ascii letters and digits of a name, i.e. `O'Brien` becomes `obrien`
+fn slug(value: &str) -> String {
+    value
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+pub fn first_name(index: usize) -> String {
+    pick(FIRST_NAMES, index, 1).to_string()
+}
+
+pub fn last_name(index: usize) -> String {
+    pick(LAST_NAMES, index, 1).to_string()
+}
+
+/// A first name followed by a last name, going through every combination of the two
+pub fn full_name(index: usize) -> String {
+    format!(
+        "{} {}",
+        pick(FIRST_NAMES, index, 1),
+        pick(LAST_NAMES, index, FIRST_NAMES.len())
+    )
+}
+
+/// An email address, which includes the index so that it's unique
+pub fn email(index: usize) -> String {
+    format!(
+        "{}.{}{}@{}",
+        slug(pick(FIRST_NAMES, index, 1)),
+        slug(pick(LAST_NAMES, index, FIRST_NAMES.len())),
+        index,
+        pick(EMAIL_DOMAINS, index, 1)
+    )
+}
+
+/// A North American phone number using the 555 exchange, i.e. `+1 415-555-0123`
+pub fn phone(index: usize) -> String {
+    format!("+1 {}-555-{:04}", pick(AREA_CODES, index, 1), index % 10_000)
+}
+
+pub fn street_address(index: usize) -> String {
+    format!(
+        "{} {} {}",
+        1 + index.wrapping_mul(37) % 9_999,
+        pick(STREET_NAMES, index, 1),
+        pick(STREET_SUFFIXES, index, STREET_NAMES.len())
+    )
+}
+
+pub fn company(index: usize) -> String {
+    format!(
+        "{} {} {}",
+        pick(COMPANY_WORDS, index, 1),
+        pick(COMPANY_INDUSTRIES, index, COMPANY_WORDS.len()),
+        pick(COMPANY_SUFFIXES, index, 1)
+    )
+}
+
+/// A lorem ipsum sentence of 4 to 12 words
+pub fn lorem(index: usize) -> String {
+    let mut rng = Rng::new(index as u64);
+    let word_count = 4 + (rng.next_u64() % 9) as usize;
+
+    let mut sentence = String::new();
+    for position in 0..word_count {
+        let word = LOREM_WORDS[(rng.next_u64() % LOREM_WORDS.len() as u64) as usize];
+        if position == 0 {
+            sentence.push_str(&word[..1].to_uppercase());
+            sentence.push_str(&word[1..]);
+        } else {
+            sentence.push(' ');
+            sentence.push_str(word);
+        }
+    }
+    sentence.push('.');
+
+    sentence
+}
+
+pub fn url(index: usize) -> String {
+    format!(
+        "https://www.{}.{}/{}/{}",
+        slug(pick(COMPANY_WORDS, index, 1)),
+        pick(EMAIL_DOMAINS, index, COMPANY_WORDS.len()),
+        pick(URL_PATHS, index, 1),
+        index
+    )
+}
+
+/// A lowercase `#rrggbb` color
+pub fn hex_color(index: usize) -> String {
+    format!("#{:06x}", Rng::new(index as u64).next_u64() & 0xFF_FFFF)
+}
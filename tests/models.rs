@@ -69,3 +69,495 @@ pub struct TypeTester {
     pub type_array_f32: [f32; 3],
     pub type_array_f64: [f64; 3],
 }
+
+#[derive(Tlayuda, Debug)]
+pub struct OptionTester {
+    pub type_option_string: Option<String>,
+    pub type_option_u32: Option<u32>,
+    pub type_option_person: Option<Person>,
+    #[tlayuda(option = "none")]
+    pub type_option_none: Option<String>,
+    #[tlayuda(option = "alternate")]
+    pub type_option_alternate: std::option::Option<u64>,
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub enum OrderStatus {
+    Pending,
+    Shipped,
+    #[tlayuda_ignore]
+    Lost,
+    Delivered,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct Order {
+    pub id: u32,
+    pub status: OrderStatus,
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub enum Event {
+    Created { id: u64, name: String },
+    Renamed(u64, String),
+    Archived,
+    OptionalNote { note: Option<String> },
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub enum Failure {
+    HTTPError { code: u16 },
+    TimedOut,
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub struct UserId(pub u64);
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub struct APIKey(pub String);
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub struct EmailAddress(pub String);
+
+#[derive(Tlayuda, Debug)]
+pub struct TupleTester(pub String, #[tlayuda(name = "count")] pub u32, pub UserId);
+
+#[derive(Tlayuda, Debug)]
+pub struct User {
+    pub id: UserId,
+    pub email: EmailAddress,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub first: Option<T>,
+    pub total: usize,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct Wrapper<T>
+where
+    T: std::fmt::Debug,
+{
+    pub value: T,
+    pub label: String,
+}
+
+#[derive(Tlayuda, Debug)]
+#[tlayuda(bound = "T: tlayuda::runtime::TlayudaDefault + std::fmt::Debug")]
+pub struct GenericTester<T: std::fmt::Debug, const N: usize> {
+    pub wrapped_u32: Wrapper<u32>,
+    pub wrapped_t: Wrapper<T>,
+    pub page: Page<String>,
+    #[tlayuda_ignore]
+    pub ignored: Vec<T>,
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+#[derive(Tlayuda, Debug)]
+#[tlayuda(leak)]
+pub struct Row<'a> {
+    pub name: &'a str,
+    pub tags: &'a [u32],
+    pub person: &'a Person,
+    pub nickname: Option<&'a str>,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct BorrowTester<'a, 'b, T: std::fmt::Debug> {
+    #[tlayuda(leak)]
+    pub title: &'a str,
+    pub values: &'b [T],
+    pub scratch: &'b mut [u8],
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct CollectionTester {
+    pub type_hashmap: std::collections::HashMap<String, u32>,
+    pub type_btreemap: std::collections::BTreeMap<u32, String>,
+    pub type_hashset: std::collections::HashSet<u64>,
+    pub type_btreeset: std::collections::BTreeSet<String>,
+    pub type_vecdeque: std::collections::VecDeque<i32>,
+    #[tlayuda(len = 3)]
+    pub populated_vec: Vec<u32>,
+    #[tlayuda(len = 2)]
+    pub populated_hashmap: std::collections::HashMap<String, u32>,
+    #[tlayuda(len = 2)]
+    pub populated_btreeset: std::collections::BTreeSet<String>,
+    #[tlayuda(len = 2)]
+    pub populated_vecdeque: Option<std::collections::VecDeque<Person>>,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct Classroom {
+    pub id: u32,
+    #[tlayuda(len = 3)]
+    pub students: Vec<Student>,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct Student {
+    pub id: u32,
+    pub name: String,
+    #[tlayuda(len = 2)]
+    pub grades: Vec<u32>,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct WrapperTester {
+    pub type_box: Box<u32>,
+    pub type_box_person: Box<Person>,
+    pub type_rc: std::rc::Rc<String>,
+    pub type_arc: std::sync::Arc<Teacher>,
+    pub type_cell: std::cell::Cell<bool>,
+    pub type_refcell: std::cell::RefCell<Vec<u8>>,
+    pub type_mutex: std::sync::Mutex<i64>,
+    pub type_rwlock: std::sync::RwLock<String>,
+    pub type_box_str: Box<str>,
+    pub type_arc_slice: std::sync::Arc<[u8]>,
+    #[tlayuda(len = 2)]
+    pub type_arc_mutex_vec: std::sync::Arc<std::sync::Mutex<Vec<u32>>>,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct Node {
+    pub value: u32,
+    pub next: Option<Box<Node>>,
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub enum Expr {
+    Lit(u32),
+    Neg(Box<Expr>),
+    Add(std::rc::Rc<Expr>, std::rc::Rc<Expr>),
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct TupleFieldTester {
+    pub pair: (String, u32),
+    pub single: (bool,),
+    pub unit: (),
+    pub nested: (u8, (String, Person), Option<i64>),
+}
+
+pub const BUFFER_SIZE: usize = 2;
+
+#[derive(Tlayuda, Debug)]
+pub struct ArrayTester<const N: usize> {
+    pub type_array_string: [String; 4],
+    pub type_array_bool: [bool; 2],
+    pub type_array_person: [Person; 3],
+    pub type_array_matrix: [[f32; 4]; 4],
+    pub type_array_const: [u8; BUFFER_SIZE],
+    pub type_array_const_expr: [char; BUFFER_SIZE * 2],
+    pub type_array_generic: [u64; N],
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct GenericArrayTester<T: std::fmt::Debug, const N: usize> {
+    pub items: [T; N],
+    pub pair: (T, u8),
+    #[tlayuda(len = 2)]
+    pub list: Vec<T>,
+    #[tlayuda(len = 2)]
+    pub lookup: std::collections::BTreeMap<u8, Option<T>>,
+}
+
+#[derive(Tlayuda, Debug)]
+#[tlayuda(leak)]
+pub struct StdTypeTester<'a> {
+    pub type_pathbuf: std::path::PathBuf,
+    pub type_path: &'a std::path::Path,
+    pub type_box_path: Box<std::path::Path>,
+    pub type_duration: std::time::Duration,
+    pub type_systemtime: std::time::SystemTime,
+    pub type_ipaddr: std::net::IpAddr,
+    pub type_ipv4addr: std::net::Ipv4Addr,
+    pub type_ipv6addr: std::net::Ipv6Addr,
+    pub type_socketaddr: std::net::SocketAddr,
+    pub type_socketaddrv4: std::net::SocketAddrV4,
+    pub type_socketaddrv6: std::net::SocketAddrV6,
+    pub type_array_duration: [std::time::Duration; 2],
+    #[tlayuda(len = 2)]
+    pub type_vec_ipv4addr: Vec<std::net::Ipv4Addr>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Sku(pub String);
+
+impl tlayuda::runtime::TlayudaDefault for Sku {
+    fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+        Sku(format!("SKU-{:04}", index))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Money {
+    pub cents: i64,
+    pub currency: String,
+}
+
+impl tlayuda::runtime::TlayudaDefault for Money {
+    fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+        Money { cents: index as i64 * 100, currency: "EUR".to_string() }
+    }
+}
+
+// shares its name with `std::time::Duration` but has its own default
+pub mod billing {
+    #[derive(Debug, PartialEq)]
+    pub struct Duration {
+        pub months: u32,
+    }
+
+    impl tlayuda::runtime::TlayudaDefault for Duration {
+        fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+            Duration { months: index as u32 * 12 }
+        }
+    }
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct DefaultsTester {
+    pub sku: Sku,
+    pub backup_sku: Option<Sku>,
+    #[tlayuda(len = 2)]
+    pub skus: Vec<Sku>,
+    pub price: Money,
+    pub person: Person,
+    pub term: billing::Duration,
+}
+
+pub const DEFAULT_COUNTRY: &str = "MX";
+
+#[derive(Tlayuda, Debug)]
+pub struct DefaultTester {
+    #[tlayuda(default = |i| format!("user{}@example.com", i))]
+    pub email: String,
+    #[tlayuda(default = 42)]
+    pub answer: u8,
+    #[tlayuda(default = i * 10)]
+    pub score: usize,
+    #[tlayuda(default = DEFAULT_COUNTRY.to_string())]
+    pub country: String,
+    #[tlayuda(default = if i == 0 { None } else { Some(i as u32) })]
+    pub referrer: Option<u32>,
+    #[tlayuda(default = |_: usize| vec!["admin".to_string()])]
+    pub roles: Vec<String>,
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub enum Shape {
+    Circle {
+        #[tlayuda(default = 1.5)]
+        radius: f32,
+    },
+    Square(#[tlayuda(default = |i| i as u32 + 100)] u32),
+}
+
+pub mod support {
+    pub fn email(index: usize) -> String {
+        format!("user{}@corp.test", index)
+    }
+
+    pub fn label(index: usize, field_name: &str) -> String {
+        format!("{}-{}", field_name.to_uppercase(), index)
+    }
+
+    pub fn doubled(index: usize) -> usize {
+        index * 2
+    }
+
+    pub fn status(index: usize) -> super::OrderStatus {
+        super::OrderStatus::tlayuda().with_index(index + 1).build()
+    }
+
+    pub fn callback(_index: usize) -> fn(u32) -> u32 {
+        |x| x + 1
+    }
+
+    pub fn parse<T: std::str::FromStr>(index: usize) -> T
+    where
+        T::Err: std::fmt::Debug,
+    {
+        index.to_string().parse().unwrap()
+    }
+}
+
+#[derive(Tlayuda)]
+pub struct WithTester {
+    #[tlayuda(with = support::email)]
+    pub email: String,
+    #[tlayuda(with = support::label)]
+    pub label: String,
+    #[tlayuda(with = support::doubled)]
+    pub doubled: usize,
+    #[tlayuda(with = support::status)]
+    pub status: OrderStatus,
+    #[tlayuda(with = support::callback)]
+    pub callback: fn(u32) -> u32,
+    #[tlayuda(with = support::parse::<i64>)]
+    pub parsed: i64,
+}
+
+#[derive(Tlayuda, Debug)]
+#[tlayuda(leak)]
+pub struct FormatTester<'a> {
+    #[tlayuda(format = "user-{i}@corp.test")]
+    pub email: String,
+    #[tlayuda(format = "{struct}.{field}.{i}")]
+    pub path: String,
+    #[tlayuda(format = "SKU-{i}-{i}")]
+    pub sku: Option<Box<str>>,
+    #[tlayuda(format = "{{literal}} {field}")]
+    pub literal: &'a str,
+    #[tlayuda(len = 2, format = "tag-{i}")]
+    pub tags: Vec<String>,
+    #[tlayuda(format = "/srv/{i}")]
+    pub root: std::path::PathBuf,
+    #[tlayuda(name = "nickname", format = "{field}{i}")]
+    pub handle: String,
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+pub enum Contact {
+    Email(#[tlayuda(format = "{struct}-{field}-{i}@corp.test")] String),
+}
+
+pub const MAX_PERCENT: u8 = 100;
+
+#[derive(Tlayuda, Debug)]
+pub struct NumericTester {
+    #[tlayuda(range = 1..=MAX_PERCENT)]
+    pub percent: u8,
+    #[tlayuda(range = -2..2)]
+    pub offset: i8,
+    #[tlayuda(range = 0.5..=2.5)]
+    pub ratio: f64,
+    #[tlayuda(range = 0.0..1.5)]
+    pub fraction: f32,
+    #[tlayuda(range = 0..=u64::MAX)]
+    pub big: u64,
+    #[tlayuda(range = i64::MIN..=i64::MAX)]
+    pub wide: i64,
+    #[tlayuda(start = 1000, step = 5)]
+    pub id: u64,
+    #[tlayuda(start = 10)]
+    pub floor: i32,
+    #[tlayuda(step = -1)]
+    pub countdown: i64,
+    #[tlayuda(step = 0.25)]
+    pub price: f32,
+    #[tlayuda(cycle = [10, 20, 30])]
+    pub tier: u16,
+    #[tlayuda(cycle = [1.5, 2.5])]
+    pub weight: Option<f32>,
+    #[tlayuda(range = 0..=9)]
+    pub digits: [u8; 4],
+    #[tlayuda(len = 3, cycle = [7, 8])]
+    pub lucky: Vec<u32>,
+    #[tlayuda(range = MAX_PERCENT..MAX_PERCENT)]
+    pub empty: u8,
+}
+
+#[derive(Tlayuda, Debug)]
+#[tlayuda(overflow = "saturate")]
+pub struct OverflowTester {
+    pub type_u8: u8,
+    pub type_i32: i32,
+    #[tlayuda(overflow = "wrap")]
+    pub type_u8_wrapped: u8,
+    #[tlayuda(start = -10)]
+    pub type_u16: u16,
+    #[tlayuda(len = 2)]
+    pub type_vec_u8: Vec<u8>,
+    pub type_u128: u128,
+    pub type_f32: f32,
+    #[tlayuda(cycle = [-1, 300])]
+    pub type_u8_cycle: u8,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct PanicOverflowTester {
+    #[tlayuda(overflow = "panic")]
+    pub type_u8: u8,
+    pub type_u16: u16,
+}
+
+#[derive(Tlayuda, Debug, PartialEq)]
+#[tlayuda(opaque)]
+pub struct OpaqueTester {
+    pub name: String,
+    pub count: u32,
+    #[tlayuda(range = 1..=6)]
+    pub die: u8,
+    pub is_active: bool,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct FakeTester<'a> {
+    #[tlayuda(fake = "first_name")]
+    pub first_name: String,
+    #[tlayuda(fake = "full_name")]
+    pub full_name: Option<String>,
+    #[tlayuda(fake = "email")]
+    pub email: String,
+    #[tlayuda(fake = "phone")]
+    pub phone: String,
+    #[tlayuda(fake = "street_address")]
+    pub address: String,
+    #[tlayuda(fake = "company")]
+    pub company: String,
+    #[tlayuda(fake = "lorem", leak)]
+    pub bio: &'a str,
+    #[tlayuda(fake = "url", len = 2)]
+    pub links: Vec<String>,
+    #[tlayuda(fake = "hex_color")]
+    pub color: std::ffi::OsString,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct RegexTester {
+    #[tlayuda(regex = r"^[A-Z]{3}-\d{4}$")]
+    pub code: String,
+    #[tlayuda(regex = "US|GB|MX|DE")]
+    pub country: String,
+    #[tlayuda(regex = "[a-z0-9]+(?:-[a-z0-9]+)*")]
+    pub slug: String,
+    #[tlayuda(regex = r"v\d\.\d{1,2}(-beta)?")]
+    pub version: Option<String>,
+    #[tlayuda(regex = "[^a-zA-Z0-9]{2}")]
+    pub symbols: std::ffi::OsString,
+    #[tlayuda(regex = "#[0-9a-f]{6}", len = 2)]
+    pub colors: Vec<String>,
+    #[tlayuda(regex = r"\S{4}")]
+    pub token: String,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct EdgeCaseTester<'a> {
+    pub name: String,
+    #[tlayuda(leak)]
+    pub label: &'a str,
+    pub score: f64,
+    pub count: i32,
+    pub initial: Option<char>,
+    #[tlayuda(regex = "[a-z]{3}")]
+    pub code: String,
+    #[tlayuda(option = "none")]
+    pub nickname: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Tlayuda, Debug)]
+pub struct EdgeCaseAttributeTester {
+    #[tlayuda(edge_cases)]
+    pub amount: u8,
+    pub quantity: u8,
+}
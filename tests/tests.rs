@@ -1,4 +1,4 @@
-mod models;
+pub mod models;
 use std::sync::{Arc, Mutex};
 
 #[test]
@@ -46,7 +46,7 @@ fn verify_able_to_build_different_objects() {
     assert_eq!(0, teacher.id);
     assert_eq!("first_name0", teacher.first_name);
     assert_eq!("last_name0", teacher.last_name);
-    assert_eq!(false, teacher.has_class);
+    assert!(!teacher.has_class);
 }
 
 #[test]
@@ -132,6 +132,7 @@ fn verify_recursive_building_vec() {
 }
 
 #[test]
+#[allow(clippy::arc_with_non_send_sync)]
 fn verify_recursive_building_vec_with_setter() {
     let person_builder = models::Person::tlayuda().set_first_name(|i| i.to_string());
     let person_builder = Arc::new(Mutex::new(person_builder));
@@ -168,7 +169,7 @@ fn verify_ignore_attribute() {
     let vec_u32: Vec<u32> = vec![100];
     let ignore_tester = models::IgnoreTester::tlayuda(true, vec_u32).build();
     assert_eq!("type_string0", ignore_tester.type_string);
-    assert_eq!(true, ignore_tester.type_bool);
+    assert!(ignore_tester.type_bool);
     assert_eq!(100, ignore_tester.type_vec_u32[0]);
 }
 
@@ -202,3 +203,913 @@ fn verify_array() {
                         .build();
     assert_eq!(0, type_tester.type_array_u32[0]);
 }
+
+#[test]
+fn verify_option() {
+    let option_tester = models::OptionTester::tlayuda().with_index(3).build();
+    assert_eq!(Some("type_option_string3".to_string()), option_tester.type_option_string);
+    assert_eq!(Some(3), option_tester.type_option_u32);
+    assert_eq!("first_name3", option_tester.type_option_person.unwrap().first_name);
+    assert_eq!(None, option_tester.type_option_none);
+}
+
+#[test]
+fn verify_option_alternate() {
+    models::OptionTester::tlayuda()
+        .build_vec(10)
+        .iter()
+        .enumerate()
+        .for_each(|(i, x)| {
+            if i % 2 == 0 {
+                assert_eq!(Some(i as u64), x.type_option_alternate);
+            } else {
+                assert_eq!(None, x.type_option_alternate);
+            }
+        });
+}
+
+#[test]
+fn verify_option_setters() {
+    let option_tester = models::OptionTester::tlayuda()
+        .set_type_option_string_none()
+        .set_type_option_none_some(|i| format!("some{}", i))
+        .set_type_option_u32(|i| if i == 0 { None } else { Some(1) })
+        .build();
+    assert_eq!(None, option_tester.type_option_string);
+    assert_eq!(Some("some0".to_string()), option_tester.type_option_none);
+    assert_eq!(None, option_tester.type_option_u32);
+}
+
+#[test]
+fn verify_enum_cycles_variants() {
+    let statuses = models::OrderStatus::tlayuda().build_vec(6);
+    assert_eq!(
+        vec![
+            models::OrderStatus::Pending,
+            models::OrderStatus::Shipped,
+            models::OrderStatus::Delivered,
+            models::OrderStatus::Pending,
+            models::OrderStatus::Shipped,
+            models::OrderStatus::Delivered,
+        ],
+        statuses
+    );
+}
+
+#[test]
+fn verify_enum_field() {
+    let orders = models::Order::tlayuda().with_index(1).build_vec(3);
+    assert_eq!(models::OrderStatus::Shipped, orders[0].status);
+    assert_eq!(models::OrderStatus::Delivered, orders[1].status);
+    assert_eq!(models::OrderStatus::Pending, orders[2].status);
+}
+
+#[test]
+fn verify_enum_data_variants() {
+    let events = models::Event::tlayuda().build_vec(4);
+    assert_eq!(
+        models::Event::Created {
+            id: 0,
+            name: "name0".to_string()
+        },
+        events[0]
+    );
+    assert_eq!(models::Event::Renamed(1, "renamed_1_1".to_string()), events[1]);
+    assert_eq!(models::Event::Archived, events[2]);
+    assert_eq!(
+        models::Event::OptionalNote {
+            note: Some("note3".to_string())
+        },
+        events[3]
+    );
+}
+
+#[test]
+fn verify_enum_variant_selector_and_setters() {
+    models::Event::tlayuda()
+        .variant_created()
+        .set_created_name(|i| format!("event{}", i))
+        .build_vec(10)
+        .iter()
+        .enumerate()
+        .for_each(|(i, x)| {
+            assert_eq!(
+                &models::Event::Created {
+                    id: i as u64,
+                    name: format!("event{}", i)
+                },
+                x
+            )
+        });
+
+    let event = models::Event::tlayuda()
+        .variant_optional_note()
+        .set_optional_note_note_none()
+        .build();
+    assert_eq!(models::Event::OptionalNote { note: None }, event);
+
+    let failure = models::Failure::tlayuda()
+        .variant_http_error()
+        .set_http_error_code(|_| 404)
+        .build();
+    assert_eq!(models::Failure::HTTPError { code: 404 }, failure);
+}
+
+#[test]
+fn verify_newtype() {
+    let ids = models::UserId::tlayuda().build_vec(3);
+    assert_eq!(
+        vec![models::UserId(0), models::UserId(1), models::UserId(2)],
+        ids
+    );
+
+    let user = models::User::tlayuda().with_index(5).build();
+    assert_eq!(models::UserId(5), user.id);
+    assert_eq!(models::EmailAddress("email_address5".to_string()), user.email);
+
+    let key = models::APIKey::tlayuda().with_index(3).build();
+    assert_eq!(models::APIKey("api_key3".to_string()), key);
+}
+
+#[test]
+fn verify_tuple_struct() {
+    let tuple_tester = models::TupleTester::tlayuda().with_index(2).build();
+    assert_eq!("tuple_tester_0_2", tuple_tester.0);
+    assert_eq!(2, tuple_tester.1);
+    assert_eq!(models::UserId(2), tuple_tester.2);
+
+    let tuple_tester = models::TupleTester::tlayuda()
+        .set_0(|i| format!("first{}", i))
+        .set_count(|_| 100)
+        .set_2(|_| models::UserId(42))
+        .build();
+    assert_eq!("first0", tuple_tester.0);
+    assert_eq!(100, tuple_tester.1);
+    assert_eq!(models::UserId(42), tuple_tester.2);
+}
+
+#[test]
+fn verify_generic_struct() {
+    let page = models::Page::<u32>::tlayuda().with_index(4).build();
+    assert_eq!(0, page.items.len());
+    assert_eq!(Some(4), page.first);
+    assert_eq!(4, page.total);
+
+    let page = models::Page::tlayuda()
+        .set_items(|i| vec![i as u64; 2])
+        .build();
+    assert_eq!(vec![0, 0], page.items);
+}
+
+#[test]
+fn verify_nested_generic_structs() {
+    let generic_tester = models::GenericTester::<String, 3>::tlayuda(vec!["a".to_string()])
+        .with_index(2)
+        .build();
+    assert_eq!(2, generic_tester.wrapped_u32.value);
+    assert_eq!("label2", generic_tester.wrapped_u32.label);
+    assert_eq!("value2", generic_tester.wrapped_t.value);
+    assert_eq!(2, generic_tester.page.total);
+    assert_eq!(vec!["a".to_string()], generic_tester.ignored);
+}
+
+#[test]
+fn verify_generic_enum() {
+    let values = models::Either::<u32, String>::tlayuda()
+        .set_right_0(|i| i.to_string())
+        .build_vec(2);
+    assert_eq!(
+        vec![models::Either::Left(0), models::Either::Right("1".to_string())],
+        values
+    );
+}
+
+#[test]
+fn verify_borrowed_fields() {
+    let rows = models::Row::tlayuda().build_vec(2);
+    assert_eq!("name1", rows[1].name);
+    assert_eq!(0, rows[1].tags.len());
+    assert_eq!("first_name1", rows[1].person.first_name);
+    assert_eq!(Some("nickname1"), rows[1].nickname);
+
+    let borrow_tester = models::BorrowTester::<u32>::tlayuda()
+        .set_title(|_| "title")
+        .build();
+    assert_eq!("title", borrow_tester.title);
+    assert_eq!(0, borrow_tester.values.len());
+    assert_eq!(0, borrow_tester.scratch.len());
+}
+
+#[test]
+fn verify_collections() {
+    let collection_tester = models::CollectionTester::tlayuda().build();
+    assert!(collection_tester.type_hashmap.is_empty());
+    assert!(collection_tester.type_btreemap.is_empty());
+    assert!(collection_tester.type_hashset.is_empty());
+    assert!(collection_tester.type_btreeset.is_empty());
+    assert!(collection_tester.type_vecdeque.is_empty());
+
+    let collection_tester = models::CollectionTester::tlayuda()
+        .set_type_btreemap(|i| vec![(i as u32, i.to_string())].into_iter().collect())
+        .build();
+    assert_eq!(Some(&"0".to_string()), collection_tester.type_btreemap.get(&0));
+}
+
+#[test]
+fn verify_populated_collections() {
+    let collection_tester = models::CollectionTester::tlayuda().with_index(1).build();
+    assert_eq!(vec![3, 4, 5], collection_tester.populated_vec);
+
+    assert_eq!(2, collection_tester.populated_hashmap.len());
+    assert_eq!(Some(&2), collection_tester.populated_hashmap.get("populated_hashmap_key2"));
+    assert_eq!(Some(&3), collection_tester.populated_hashmap.get("populated_hashmap_key3"));
+
+    assert!(collection_tester.populated_btreeset.contains("populated_btreeset2"));
+    assert!(collection_tester.populated_btreeset.contains("populated_btreeset3"));
+
+    let people = collection_tester.populated_vecdeque.unwrap();
+    assert_eq!("first_name2", people[0].first_name);
+    assert_eq!("first_name3", people[1].first_name);
+}
+
+#[test]
+fn verify_populated_nested_vec() {
+    let classrooms = models::Classroom::tlayuda().build_vec(2);
+
+    let student_ids = classrooms
+        .iter()
+        .flat_map(|c| c.students.iter().map(|s| s.id))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![0, 1, 2, 3, 4, 5], student_ids);
+
+    let grades = classrooms
+        .iter()
+        .flat_map(|c| c.students.iter().flat_map(|s| s.grades.iter().copied()))
+        .collect::<Vec<_>>();
+    assert_eq!((0..12).collect::<Vec<_>>(), grades);
+    assert_eq!("name4", classrooms[1].students[1].name);
+}
+
+#[test]
+fn verify_populated_nested_vec_each_setter() {
+    models::Classroom::tlayuda()
+        .set_students_each(|classroom, position| {
+            models::Student::tlayuda()
+                .set_name(move |_| format!("classroom{}-student{}", classroom, position))
+                .with_index(classroom * 3 + position)
+                .build()
+        })
+        .build_vec(10)
+        .iter()
+        .enumerate()
+        .for_each(|(i, classroom)| {
+            assert_eq!(3, classroom.students.len());
+            classroom.students.iter().enumerate().for_each(|(position, student)| {
+                assert_eq!((i * 3 + position) as u32, student.id);
+                assert_eq!(format!("classroom{}-student{}", i, position), student.name);
+            });
+        });
+}
+
+#[test]
+fn verify_smart_pointers_and_cells() {
+    let wrapper_tester = models::WrapperTester::tlayuda().with_index(1).build();
+    assert_eq!(1, *wrapper_tester.type_box);
+    assert_eq!("first_name1", wrapper_tester.type_box_person.first_name);
+    assert_eq!("type_rc1", *wrapper_tester.type_rc);
+    assert_eq!(1, wrapper_tester.type_arc.id);
+    assert!(!wrapper_tester.type_cell.get());
+    assert!(wrapper_tester.type_refcell.borrow().is_empty());
+    assert_eq!(1, *wrapper_tester.type_mutex.lock().unwrap());
+    assert_eq!("type_rwlock1", *wrapper_tester.type_rwlock.read().unwrap());
+    assert_eq!("type_box_str1", &*wrapper_tester.type_box_str);
+    assert!(wrapper_tester.type_arc_slice.is_empty());
+    assert_eq!(vec![2, 3], *wrapper_tester.type_arc_mutex_vec.lock().unwrap());
+}
+
+#[test]
+fn verify_self_referential_struct() {
+    let node = models::Node::tlayuda().with_index(1).build();
+    assert_eq!(1, node.value);
+    assert!(node.next.is_none());
+
+    let node = models::Node::tlayuda()
+        .set_next_some(|i| Box::new(models::Node::tlayuda().with_index(i + 1).build()))
+        .build();
+    assert_eq!(1, node.next.unwrap().value);
+}
+
+#[test]
+fn verify_self_referential_enum() {
+    use models::Expr;
+
+    assert_eq!(
+        vec![
+            Expr::Lit(0),
+            Expr::Neg(Box::new(Expr::Lit(1))),
+            Expr::Add(std::rc::Rc::new(Expr::Lit(2)), std::rc::Rc::new(Expr::Lit(2))),
+        ],
+        Expr::tlayuda().build_vec(3)
+    );
+}
+
+#[test]
+fn verify_tuple_fields() {
+    let tuple_field_tester = models::TupleFieldTester::tlayuda().with_index(3).build();
+    assert_eq!(("pair_0_3".to_string(), 3), tuple_field_tester.pair);
+    assert_eq!((false,), tuple_field_tester.single);
+    assert_eq!(3, tuple_field_tester.nested.0);
+    assert_eq!("nested_1_0_3", (tuple_field_tester.nested.1).0);
+    assert_eq!("first_name3", (tuple_field_tester.nested.1).1.first_name);
+    assert_eq!(Some(3), tuple_field_tester.nested.2);
+
+    let tuple_field_tester = models::TupleFieldTester::tlayuda()
+        .set_pair(|i| (format!("key{}", i), 100))
+        .build();
+    assert_eq!(("key0".to_string(), 100), tuple_field_tester.pair);
+}
+
+#[test]
+fn verify_array_elements_are_distinct() {
+    let type_tester = models::TypeTester::tlayuda().with_index(1).build();
+    assert_eq!([3, 4, 5], type_tester.type_array_u32);
+    assert_eq!([3.0, 4.0, 5.0], type_tester.type_array_f64);
+}
+
+#[test]
+fn verify_array_element_types() {
+    let array_tester = models::ArrayTester::<3>::tlayuda().with_index(1).build();
+    assert_eq!(
+        ["type_array_string4", "type_array_string5", "type_array_string6", "type_array_string7"],
+        array_tester.type_array_string
+    );
+    assert_eq!([false, false], array_tester.type_array_bool);
+    assert_eq!("first_name3", array_tester.type_array_person[0].first_name);
+    assert_eq!("first_name5", array_tester.type_array_person[2].first_name);
+    assert_eq!([16.0, 17.0, 18.0, 19.0], array_tester.type_array_matrix[0]);
+    assert_eq!([28.0, 29.0, 30.0, 31.0], array_tester.type_array_matrix[3]);
+    assert_eq!([2, 3], array_tester.type_array_const);
+    assert_eq!(['4', '5', '6', '7'], array_tester.type_array_const_expr);
+    assert_eq!([3, 4, 5], array_tester.type_array_generic);
+
+    let generic_array_tester = models::GenericArrayTester::<u32, 3>::tlayuda().with_index(1).build();
+    assert_eq!([3, 4, 5], generic_array_tester.items);
+    assert_eq!((1, 1), generic_array_tester.pair);
+    assert_eq!(vec![2, 3], generic_array_tester.list);
+    assert_eq!(Some(&Some(3)), generic_array_tester.lookup.get(&3));
+}
+
+#[test]
+fn verify_std_types() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let std_type_tester = models::StdTypeTester::tlayuda().with_index(258).build();
+    assert_eq!(PathBuf::from("type_pathbuf258"), std_type_tester.type_pathbuf);
+    assert_eq!(Path::new("type_path258"), std_type_tester.type_path);
+    assert_eq!(Path::new("type_box_path258"), &*std_type_tester.type_box_path);
+    assert_eq!(Duration::from_secs(258), std_type_tester.type_duration);
+    assert_eq!(UNIX_EPOCH + Duration::from_secs(258), std_type_tester.type_systemtime);
+    assert_eq!(IpAddr::V4(Ipv4Addr::new(0, 0, 1, 2)), std_type_tester.type_ipaddr);
+    assert_eq!(Ipv4Addr::new(0, 0, 1, 2), std_type_tester.type_ipv4addr);
+    assert_eq!(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 258), std_type_tester.type_ipv6addr);
+    assert_eq!(
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 1, 2)), 258),
+        std_type_tester.type_socketaddr
+    );
+    assert_eq!(
+        SocketAddrV4::new(Ipv4Addr::new(0, 0, 1, 2), 258),
+        std_type_tester.type_socketaddrv4
+    );
+    assert_eq!(
+        SocketAddrV6::new(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 258), 258, 0, 0),
+        std_type_tester.type_socketaddrv6
+    );
+    assert_eq!(
+        [Duration::from_secs(516), Duration::from_secs(517)],
+        std_type_tester.type_array_duration
+    );
+    assert_eq!(
+        vec![Ipv4Addr::new(0, 0, 2, 4), Ipv4Addr::new(0, 0, 2, 5)],
+        std_type_tester.type_vec_ipv4addr
+    );
+}
+
+#[test]
+fn verify_tlayuda_default_fallback() {
+    let defaults_tester = models::DefaultsTester::tlayuda().with_index(7).build();
+    assert_eq!("SKU-0007", defaults_tester.sku.0);
+    assert_eq!(Some(models::Sku("SKU-0007".to_string())), defaults_tester.backup_sku);
+    assert_eq!(vec![models::Sku("SKU-0014".to_string()), models::Sku("SKU-0015".to_string())], defaults_tester.skus);
+    assert_eq!(models::Money { cents: 700, currency: "EUR".to_string() }, defaults_tester.price);
+    assert_eq!("first_name7", defaults_tester.person.first_name);
+    assert_eq!(models::billing::Duration { months: 84 }, defaults_tester.term);
+}
+
+#[test]
+fn verify_derived_types_implement_tlayuda_default() {
+    use tlayuda::runtime::TlayudaDefault;
+
+    let person = models::Person::tlayuda_default(3, "person");
+    assert_eq!("first_name3", person.first_name);
+
+    let status = models::OrderStatus::tlayuda_default(1, "status");
+    assert_eq!(models::OrderStatus::tlayuda().with_index(1).build(), status);
+
+    let page = models::Page::<String>::tlayuda_default(5, "page");
+    assert_eq!(Some("first5".to_string()), page.first);
+}
+
+#[test]
+fn verify_element_index_sequence() {
+    use tlayuda::runtime::element_index;
+
+    assert_eq!(0, element_index(0, 3, 0));
+    assert_eq!(7, element_index(2, 3, 1));
+    assert_eq!(usize::MAX, element_index(0, 3, usize::MAX));
+    assert_eq!(0, element_index(usize::MAX, 1, 1));
+}
+
+fn build_pair<B: tlayuda::TlayudaBuilder>(builder: B) -> Vec<B::Output> {
+    builder.with_index(5).build_vec(2)
+}
+
+fn first_of<T: tlayuda::Tlayuda>() -> T {
+    use tlayuda::TlayudaBuilder;
+    T::tlayuda().build()
+}
+
+#[test]
+fn verify_builder_trait() {
+    let people = build_pair(models::Person::tlayuda());
+    assert_eq!("first_name5", people[0].first_name);
+    assert_eq!("first_name6", people[1].first_name);
+
+    let statuses = build_pair(models::OrderStatus::tlayuda());
+    assert_eq!(models::OrderStatus::tlayuda().with_index(5).build_vec(2), statuses);
+
+    let ignore_testers = build_pair(models::IgnoreTester::tlayuda(true, vec![1]));
+    assert_eq!(6, ignore_testers[1].type_i8);
+    assert_eq!(vec![1], ignore_testers[1].type_vec_u32);
+}
+
+#[test]
+fn verify_tlayuda_trait() {
+    let person: models::Person = first_of();
+    assert_eq!("first_name0", person.first_name);
+
+    let page = first_of::<models::Page<String>>();
+    assert_eq!(Some("first0".to_string()), page.first);
+
+    let status = first_of::<models::OrderStatus>();
+    assert_eq!(models::OrderStatus::tlayuda().build(), status);
+}
+
+#[test]
+fn verify_default_attribute() {
+    let defaults = models::DefaultTester::tlayuda().build_vec(2);
+    assert_eq!("user0@example.com", defaults[0].email);
+    assert_eq!("user1@example.com", defaults[1].email);
+    assert_eq!(42, defaults[1].answer);
+    assert_eq!(10, defaults[1].score);
+    assert_eq!("MX", defaults[1].country);
+    assert_eq!(None, defaults[0].referrer);
+    assert_eq!(Some(1), defaults[1].referrer);
+    assert_eq!(vec!["admin"], defaults[1].roles);
+
+    let shapes = models::Shape::tlayuda().build_vec(2);
+    assert_eq!(
+        vec![models::Shape::Circle { radius: 1.5 }, models::Shape::Square(101)],
+        shapes
+    );
+}
+
+#[test]
+fn verify_setters_override_default_attribute() {
+    let default_tester = models::DefaultTester::tlayuda()
+        .set_email(|i| format!("admin{}@corp.test", i))
+        .set_answer(|_| 7)
+        .set_referrer_none()
+        .with_index(3)
+        .build();
+    assert_eq!("admin3@corp.test", default_tester.email);
+    assert_eq!(7, default_tester.answer);
+    assert_eq!(None, default_tester.referrer);
+    assert_eq!(30, default_tester.score);
+}
+
+#[test]
+fn verify_with_attribute() {
+    let with_tester = models::WithTester::tlayuda().with_index(4).build();
+    assert_eq!("user4@corp.test", with_tester.email);
+    assert_eq!("LABEL-4", with_tester.label);
+    assert_eq!(8, with_tester.doubled);
+    assert_eq!(models::OrderStatus::tlayuda().with_index(5).build(), with_tester.status);
+    assert_eq!(2, (with_tester.callback)(1));
+    assert_eq!(4, with_tester.parsed);
+}
+
+#[test]
+fn verify_setters_override_with_attribute() {
+    let with_tester = models::WithTester::tlayuda()
+        .set_email(|_| "fixed@corp.test".to_string())
+        .set_callback(|_| |x| x * 10)
+        .build();
+    assert_eq!("fixed@corp.test", with_tester.email);
+    assert_eq!(10, (with_tester.callback)(1));
+    assert_eq!("LABEL-0", with_tester.label);
+}
+
+#[test]
+fn verify_format_attribute() {
+    let format_tester = models::FormatTester::tlayuda().with_index(3).build();
+    assert_eq!("user-3@corp.test", format_tester.email);
+    assert_eq!("FormatTester.path.3", format_tester.path);
+    assert_eq!(Some("SKU-3-3".into()), format_tester.sku);
+    assert_eq!("{literal} literal", format_tester.literal);
+    assert_eq!(vec!["tag-6", "tag-7"], format_tester.tags);
+    assert_eq!(std::path::PathBuf::from("/srv/3"), format_tester.root);
+    assert_eq!("nickname3", format_tester.handle);
+
+    let contact = models::Contact::tlayuda().with_index(1).build();
+    assert_eq!(models::Contact::Email("Contact-email_0_-1@corp.test".to_string()), contact);
+}
+
+#[test]
+fn verify_setters_override_format_attribute() {
+    let format_tester = models::FormatTester::tlayuda()
+        .set_email(|_| "fixed@corp.test".to_string())
+        .build();
+    assert_eq!("fixed@corp.test", format_tester.email);
+    assert_eq!("user-0@corp.test", models::FormatTester::tlayuda().build().email);
+}
+
+#[test]
+fn verify_numeric_range_attribute() {
+    let numeric_testers = models::NumericTester::tlayuda()
+        .set_empty(|_| 0)
+        .build_vec(300);
+    assert!(numeric_testers.iter().all(|n| (1..=100).contains(&n.percent)));
+    assert_eq!(1, numeric_testers[0].percent);
+    assert_eq!(100, numeric_testers[99].percent);
+    assert_eq!(1, numeric_testers[100].percent);
+    assert_eq!(
+        vec![-2, -1, 0, 1, -2],
+        numeric_testers.iter().take(5).map(|n| n.offset).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![0.5, 1.5, 2.5, 0.5],
+        numeric_testers.iter().take(4).map(|n| n.ratio).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![0.0, 1.0, 0.0],
+        numeric_testers.iter().take(3).map(|n| n.fraction).collect::<Vec<_>>()
+    );
+    assert_eq!(299, numeric_testers[299].big);
+    assert_eq!(i64::MIN + 299, numeric_testers[299].wide);
+    assert_eq!([4, 5, 6, 7], numeric_testers[1].digits);
+    assert_eq!([8, 9, 0, 1], numeric_testers[2].digits);
+}
+
+#[test]
+fn verify_numeric_sequence_and_cycle_attributes() {
+    let numeric_tester = models::NumericTester::tlayuda()
+        .set_empty(|_| 0)
+        .with_index(4)
+        .build();
+    assert_eq!(1020, numeric_tester.id);
+    assert_eq!(14, numeric_tester.floor);
+    assert_eq!(-4, numeric_tester.countdown);
+    assert_eq!(1.0, numeric_tester.price);
+    assert_eq!(20, numeric_tester.tier);
+    assert_eq!(Some(1.5), numeric_tester.weight);
+    assert_eq!(vec![7, 8, 7], numeric_tester.lucky);
+}
+
+#[test]
+#[should_panic(expected = "Tlayuda can't generate `empty` from an empty range")]
+fn verify_empty_numeric_range_panics() {
+    models::NumericTester::tlayuda().build();
+}
+
+#[test]
+fn verify_saturating_overflow() {
+    let overflow_testers = models::OverflowTester::tlayuda().with_index(254).build_vec(3);
+    assert_eq!(
+        vec![254, 255, 255],
+        overflow_testers.iter().map(|o| o.type_u8).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![254, 255, 0],
+        overflow_testers.iter().map(|o| o.type_u8_wrapped).collect::<Vec<_>>()
+    );
+    assert_eq!(244, overflow_testers[0].type_u16);
+    assert_eq!(vec![255, 255], overflow_testers[0].type_vec_u8);
+    assert_eq!(256, overflow_testers[2].type_u128);
+    assert_eq!(
+        vec![0, 255, 0],
+        overflow_testers.iter().map(|o| o.type_u8_cycle).collect::<Vec<_>>()
+    );
+
+    let overflow_tester = models::OverflowTester::tlayuda().with_index(usize::MAX).build();
+    assert_eq!(i32::MAX, overflow_tester.type_i32);
+    assert_eq!(usize::MAX as u128, overflow_tester.type_u128);
+
+    let overflow_tester = models::OverflowTester::tlayuda().build();
+    assert_eq!(0, overflow_tester.type_u16);
+}
+
+#[test]
+fn verify_wrapping_overflow_by_default() {
+    let panic_overflow_tester = models::PanicOverflowTester::tlayuda()
+        .set_type_u8(|_| 0)
+        .with_index(65_537)
+        .build();
+    assert_eq!(1, panic_overflow_tester.type_u16);
+}
+
+#[test]
+#[should_panic(expected = "Tlayuda can't fit 256 into `u8` for field `type_u8` at index 256")]
+fn verify_panicking_overflow() {
+    let panic_overflow_testers = models::PanicOverflowTester::tlayuda().build_vec(256);
+    assert_eq!(255, panic_overflow_testers[255].type_u8);
+
+    models::PanicOverflowTester::tlayuda().with_index(256).build();
+}
+
+#[test]
+fn verify_seeded_rng_is_splitmix64() {
+    let mut rng = tlayuda::runtime::Rng::new(0);
+    assert_eq!(0xE220_A839_7B1D_CDAF, rng.next_u64());
+    assert_eq!(0x6E78_9E6A_A1B9_65F4, rng.next_u64());
+    assert_eq!(0x6E78_9E6A_A1B9_65F4, tlayuda::runtime::Rng::new(0).nth_u64(1));
+}
+
+#[test]
+fn verify_with_seed_is_reproducible() {
+    let people = models::Person::tlayuda().with_seed(7).build_vec(3);
+    let replayed = models::Person::tlayuda().with_seed(7).build_vec(3);
+    for (person, replay) in people.iter().zip(replayed.iter()) {
+        assert_eq!(person.first_name, replay.first_name);
+        assert_eq!(person.last_name, replay.last_name);
+    }
+
+    assert_ne!("first_name0", people[0].first_name);
+    assert_ne!(people[0].first_name, people[1].first_name);
+    assert_ne!(
+        people[0].first_name.trim_start_matches("first_name"),
+        people[0].last_name.trim_start_matches("last_name")
+    );
+
+    let other_seed = models::Person::tlayuda().with_seed(8).build();
+    assert_ne!(people[0].first_name, other_seed.first_name);
+
+    // pinned so that a change to the generated streams is noticed
+    let expected = tlayuda::runtime::Rng::for_field(7, "first_name").nth_u64(0) as u32;
+    assert_eq!(format!("first_name{}", expected), people[0].first_name);
+}
+
+#[test]
+fn verify_with_seed_keeps_setters_and_attributes() {
+    let numeric_testers = models::NumericTester::tlayuda()
+        .set_empty(|_| 0)
+        .set_id(|i| i as u64)
+        .with_seed(99)
+        .build_vec(50);
+    assert_eq!(49, numeric_testers[49].id);
+    assert!(numeric_testers.iter().all(|n| (1..=100).contains(&n.percent)));
+    assert!(numeric_testers.iter().all(|n| [10, 20, 30].contains(&n.tier)));
+    assert!(numeric_testers.iter().any(|n| n.percent != numeric_testers[0].percent));
+
+    let statuses = models::OrderStatus::tlayuda().with_seed(3).build_vec(20);
+    let replayed = models::OrderStatus::tlayuda().with_seed(3).build_vec(20);
+    assert_eq!(statuses, replayed);
+}
+
+#[test]
+fn verify_with_seed_keeps_the_index_for_index_based_attributes() {
+    let defaults = models::DefaultTester::tlayuda().with_seed(5).build_vec(3);
+    assert_eq!(vec![0, 10, 20], defaults.iter().map(|d| d.score).collect::<Vec<_>>());
+    assert_eq!("user2@example.com", defaults[2].email);
+
+    let numeric_testers = models::NumericTester::tlayuda()
+        .set_empty(|_| 0)
+        .with_seed(5)
+        .build_vec(3);
+    assert_eq!(
+        vec![1000, 1005, 1010],
+        numeric_testers.iter().map(|n| n.id).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![10, 20, 30],
+        numeric_testers.iter().map(|n| n.tier).collect::<Vec<_>>()
+    );
+
+    let option_testers = models::OptionTester::tlayuda().with_seed(3).build_vec(6);
+    assert_eq!(
+        vec![true, false, true, false, true, false],
+        option_testers.iter().map(|o| o.type_option_alternate.is_some()).collect::<Vec<_>>()
+    );
+    let expected = tlayuda::runtime::Rng::for_field(3, "type_option_alternate").nth_u64(2) as u32;
+    assert_eq!(Some(expected as u64), option_testers[2].type_option_alternate);
+}
+
+#[test]
+fn verify_with_seed_fits_small_numeric_types() {
+    let panic_overflow_testers = models::PanicOverflowTester::tlayuda().with_seed(11).build_vec(100);
+    assert!(panic_overflow_testers.iter().any(|p| p.type_u8 != panic_overflow_testers[0].type_u8));
+
+    let type_testers = models::TypeTester::tlayuda().with_seed(11).build_vec(20);
+    assert!(type_testers.iter().all(|t| t.type_char.is_ascii_digit()));
+
+    let overflow_testers = models::OverflowTester::tlayuda().with_seed(11).build_vec(100);
+    assert!(overflow_testers.iter().any(|o| o.type_u8 != 255));
+    assert!(overflow_testers.iter().any(|o| o.type_i32 != i32::MAX));
+    assert!(overflow_testers.iter().flat_map(|o| &o.type_vec_u8).any(|v| *v != 255));
+}
+
+#[test]
+fn verify_opaque_defaults_are_seeded_per_thread() {
+    let seed = tlayuda::runtime::opaque_seed();
+    let opaque_testers = models::OpaqueTester::tlayuda().build_vec(20);
+    assert_eq!(opaque_testers, models::OpaqueTester::tlayuda().build_vec(20));
+
+    let expected = tlayuda::runtime::Rng::for_field(seed, "name").nth_u64(0) as u32;
+    assert_eq!(format!("name{}", expected), opaque_testers[0].name);
+    assert!(opaque_testers.iter().all(|o| (1..=6).contains(&o.die)));
+    assert!(opaque_testers.iter().any(|o| o.is_active));
+    assert!(opaque_testers.iter().any(|o| !o.is_active));
+
+    let reseeded = models::OpaqueTester::tlayuda().with_seed(7).build();
+    let expected = tlayuda::runtime::Rng::for_field(7, "name").nth_u64(0) as u32;
+    assert_eq!(format!("name{}", expected), reseeded.name);
+
+    let opaque_tester = models::OpaqueTester::tlayuda()
+        .set_name(|i| format!("opaque{}", i))
+        .with_index(4)
+        .build();
+    assert_eq!("opaque4", opaque_tester.name);
+
+    // other builders keep their predictable defaults
+    assert_eq!("first_name0", models::Person::tlayuda().build().first_name);
+}
+
+#[test]
+fn verify_opaque_seed_is_reported_for_caught_panics() {
+    if std::env::var_os("TLAYUDA_PANIC_CHILD").is_some() {
+        models::OpaqueTester::tlayuda().build();
+        let result = std::panic::catch_unwind(|| panic!("expected"));
+        assert!(result.is_err());
+        return;
+    }
+
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args([
+            "--exact",
+            "verify_opaque_seed_is_reported_for_caught_panics",
+            "--nocapture",
+        ])
+        .env("TLAYUDA_PANIC_CHILD", "1")
+        .env("TLAYUDA_SEED", "42")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("run with TLAYUDA_SEED=42 to reproduce"));
+}
+
+#[test]
+fn verify_fake_attribute_values() {
+    use tlayuda::runtime::fake;
+
+    let fake_testers = models::FakeTester::tlayuda().build_vec(50);
+    assert_eq!(fake::first_name(3), fake_testers[3].first_name);
+    assert_eq!(Some(fake::full_name(3)), fake_testers[3].full_name);
+    assert_eq!(fake::lorem(3), fake_testers[3].bio);
+    assert_eq!(vec![fake::url(6), fake::url(7)], fake_testers[3].links);
+    assert_eq!(fake::hex_color(3), fake_testers[3].color.to_str().unwrap());
+    assert_ne!(fake_testers[0].first_name, fake_testers[1].first_name);
+
+    for (i, fake_tester) in fake_testers.iter().enumerate() {
+        let (local, domain) = fake_tester.email.split_once('@').unwrap();
+        assert!(local.ends_with(&i.to_string()));
+        assert!(domain.starts_with("example."));
+        assert!(local.chars().all(|c| c.is_ascii_alphanumeric() || c == '.'));
+
+        assert!(fake_tester.phone.starts_with("+1 "));
+        assert_eq!(15, fake_tester.phone.len());
+        assert!(fake_tester.address.split(' ').next().unwrap().parse::<u32>().is_ok());
+        assert!(fake_tester.company.split(' ').count() >= 3);
+        assert!(fake_tester.bio.ends_with('.'));
+        assert!(fake_tester.bio.chars().next().unwrap().is_uppercase());
+        assert!(fake_tester.links[0].starts_with("https://www."));
+    }
+
+    let seeded = models::FakeTester::tlayuda().with_seed(11).build();
+    let replayed = models::FakeTester::tlayuda().with_seed(11).build();
+    assert_eq!(seeded.email, replayed.email);
+    assert_ne!(fake_testers[0].email, seeded.email);
+}
+
+#[test]
+fn verify_regex_attribute_values() {
+    let regex_testers = models::RegexTester::tlayuda().build_vec(200);
+    assert!(regex_testers[1].code.starts_with('B'));
+    assert!(regex_testers[27].code.starts_with("BB"));
+    assert_eq!(
+        vec!["US", "GB", "MX", "DE"],
+        regex_testers[..4].iter().map(|r| r.country.as_str()).collect::<Vec<_>>()
+    );
+    assert_eq!(regex_testers[5].code, models::RegexTester::tlayuda().with_index(5).build().code);
+
+    let codes = regex_testers.iter().map(|r| &r.code).collect::<std::collections::HashSet<_>>();
+    assert_eq!(200, codes.len());
+
+    for regex_tester in regex_testers.iter() {
+        let (letters, digits) = regex_tester.code.split_once('-').unwrap();
+        assert!(letters.len() == 3 && letters.chars().all(|c| c.is_ascii_uppercase()));
+        assert!(digits.len() == 4 && digits.chars().all(|c| c.is_ascii_digit()));
+
+        assert!(["US", "GB", "MX", "DE"].contains(&regex_tester.country.as_str()));
+
+        assert!(!regex_tester.slug.starts_with('-') && !regex_tester.slug.ends_with('-'));
+        assert!(!regex_tester.slug.contains("--"));
+        assert!(regex_tester.slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'));
+
+        let version = regex_tester.version.as_ref().unwrap();
+        let version = version.strip_suffix("-beta").unwrap_or(version);
+        let (major, minor) = version.strip_prefix('v').unwrap().split_once('.').unwrap();
+        assert!(major.len() == 1 && major.parse::<u8>().is_ok());
+        assert!((1..=2).contains(&minor.len()) && minor.parse::<u8>().is_ok());
+
+        let symbols = regex_tester.symbols.to_str().unwrap();
+        assert_eq!(2, symbols.len());
+        assert!(symbols.chars().all(|c| !c.is_ascii_alphanumeric() && !c.is_ascii_control()));
+
+        assert_eq!(4, regex_tester.token.len());
+        assert!(regex_tester.token.chars().all(|c| c.is_ascii_graphic()));
+
+        for color in regex_tester.colors.iter() {
+            assert_eq!(7, color.len());
+            assert!(color[1..].chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+        }
+    }
+
+    // later choices, like the slug's `-` parts, take larger indexes to reach
+    let regex_tester = models::RegexTester::tlayuda().with_index(324).build();
+    assert_eq!("a-a", regex_tester.slug);
+    let regex_tester = models::RegexTester::tlayuda().with_index(200).build();
+    assert_eq!(Some("v0.0-beta"), regex_tester.version.as_deref());
+}
+
+#[test]
+fn verify_with_edge_cases_sweeps_edge_cases_first() {
+    use tlayuda::runtime::EdgeCases;
+
+    let edge_case_testers = models::EdgeCaseTester::tlayuda().with_edge_cases().build_vec(20);
+    let strings = (0..).map_while(String::edge_case).collect::<Vec<_>>();
+    for (i, string) in strings.iter().enumerate() {
+        assert_eq!(string, &edge_case_testers[i].name);
+        assert_eq!(string, edge_case_testers[i].label);
+    }
+    assert_eq!(format!("name{}", strings.len()), edge_case_testers[strings.len()].name);
+
+    assert_eq!(0.0, edge_case_testers[0].score);
+    assert!(edge_case_testers[3].score.is_nan());
+    assert_eq!(f64::INFINITY, edge_case_testers[4].score);
+    assert_eq!(10.0, edge_case_testers[10].score);
+    assert_eq!(vec![0, 1, -1, i32::MIN, i32::MAX], edge_case_testers[..5].iter().map(|e| e.count).collect::<Vec<_>>());
+    assert_eq!(None, edge_case_testers[0].initial);
+    assert_eq!(Some('\0'), edge_case_testers[1].initial);
+    assert!(edge_case_testers.iter().any(|e| e.name.chars().count() > 1000));
+    assert!(edge_case_testers.iter().any(|e| e.name.contains('🎉')));
+
+    // fields decided by another attribute or without edge cases keep their values
+    assert!(edge_case_testers.iter().all(|e| e.code.len() == 3));
+    assert!(edge_case_testers.iter().all(|e| e.nickname.is_none()));
+    assert!(edge_case_testers.iter().all(|e| e.tags.is_empty()));
+
+    let edge_case_tester = models::EdgeCaseTester::tlayuda()
+        .set_name(|i| format!("set{}", i))
+        .with_edge_cases()
+        .build();
+    assert_eq!("set0", edge_case_tester.name);
+
+    let edge_case_tester = models::EdgeCaseTester::tlayuda().build();
+    assert_eq!("name0", edge_case_tester.name);
+}
+
+#[test]
+fn verify_edge_cases_attribute() {
+    let edge_case_attribute_testers = models::EdgeCaseAttributeTester::tlayuda().build_vec(5);
+    assert_eq!(
+        vec![0, 1, u8::MAX, 3, 4],
+        edge_case_attribute_testers.iter().map(|e| e.amount).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![0, 1, 2, 3, 4],
+        edge_case_attribute_testers.iter().map(|e| e.quantity).collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,572 @@
+use crate::regex::RegexPattern;
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, Ident, Lit, Meta, Token};
+
+/// A single `key` or `key = value` entry inside of a `#[tlayuda(...)]` attribute
+struct TlayudaArgument {
+    key: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for TlayudaArgument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(TlayudaArgument { key, value })
+    }
+}
+
+/// Settings read from the `#[tlayuda(...)]` attribute placed above a struct or enum
+#[derive(Debug, Default)]
+pub struct ContainerAttributes {
+    // extra where-clause predicates added to the generated builder
+    pub bound: Vec<syn::WherePredicate>,
+    // allows borrowed fields on the whole item to be backed by leaked allocations
+    pub leak: bool,
+    // what happens when a numeric value doesn't fit in a field's type
+    pub overflow: Option<OverflowPolicy>,
+    // seeds dynamic defaults unpredictably so tests can't depend on them
+    pub opaque: bool,
+}
+
+impl ContainerAttributes {
+    pub fn from_attributes(attributes: &[Attribute]) -> syn::Result<ContainerAttributes> {
+        let mut container_attributes = ContainerAttributes::default();
+
+        for attribute in attributes.iter().filter(|a| a.path.is_ident("tlayuda")) {
+            for argument in parse_arguments(attribute)? {
+                match argument.key.to_string().as_str() {
+                    "bound" => {
+                        let predicates = string_value(&argument)?.parse_with(
+                            Punctuated::<syn::WherePredicate, Token![,]>::parse_terminated,
+                        )?;
+                        container_attributes.bound.extend(predicates);
+                    }
+                    "leak" => container_attributes.leak = true,
+                    "opaque" => container_attributes.opaque = true,
+                    "overflow" => {
+                        container_attributes.overflow = Some(parse_overflow_policy(&argument)?)
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            argument.key.span(),
+                            format!("Unknown tlayuda attribute `{}`", argument.key),
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(container_attributes)
+    }
+}
+
+/// Settings read from the attributes placed above an enum variant
+#[derive(Debug, Default)]
+pub struct VariantAttributes {
+    pub is_ignored: bool,
+}
+
+impl VariantAttributes {
+    pub fn from_attributes(attributes: &[Attribute]) -> syn::Result<VariantAttributes> {
+        let mut variant_attributes = VariantAttributes::default();
+
+        for attribute in attributes {
+            if attribute.path.is_ident("tlayuda_ignore") {
+                if let Ok(Meta::Path(_)) = attribute.parse_meta() {
+                    variant_attributes.is_ignored = true;
+                }
+            } else if attribute.path.is_ident("tlayuda") {
+                // every tlayuda setting applies to fields, so none of them can be used here
+                if let Some(argument) = parse_arguments(attribute)?.first() {
+                    return Err(syn::Error::new(
+                        argument.key.span(),
+                        format!(
+                            "`{}` can't be used on a variant, only tlayuda_ignore is supported",
+                            argument.key
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(variant_attributes)
+    }
+}
+
+/// Controls how the builder populates an `Option<T>` field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionStrategy {
+    Some,
+    None,
+    Alternate,
+}
+
+/// Controls what happens when a numeric value doesn't fit in the field's type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverflowPolicy {
+    Wrap,
+    Saturate,
+    Panic,
+}
+
+/// A piece of a `format` attribute's template
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatSegment {
+    Literal(String),
+    // `{i}`, the index of the object being built
+    Index,
+    // `{field}`, the name of the field
+    Field,
+    // `{struct}`, the name of the struct or enum
+    Struct,
+}
+
+/// The template of a `format` attribute, i.e. `"user-{i}@corp.test"`
+#[derive(Debug)]
+pub struct FormatTemplate {
+    pub literal: syn::LitStr,
+    pub segments: Vec<FormatSegment>,
+}
+
+impl FormatTemplate {
+    fn parse(value: &syn::LitStr) -> syn::Result<FormatTemplate> {
+        let template = value.value();
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => {
+                                return Err(syn::Error::new(
+                                    value.span(),
+                                    "Unclosed `{` in format, use `{{` for a literal brace",
+                                ))
+                            }
+                        }
+                    }
+
+                    let segment = match placeholder.as_str() {
+                        "i" => FormatSegment::Index,
+                        "field" => FormatSegment::Field,
+                        "struct" => FormatSegment::Struct,
+                        _ => {
+                            return Err(syn::Error::new(
+                                value.span(),
+                                format!(
+                                    "Unknown placeholder `{{{}}}`, expected `{{i}}`, `{{field}}` or `{{struct}}`",
+                                    placeholder
+                                ),
+                            ))
+                        }
+                    };
+
+                    if !literal.is_empty() {
+                        segments.push(FormatSegment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(segment);
+                }
+                '}' => {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "Unmatched `}` in format, use `}}` for a literal brace",
+                    ))
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(FormatSegment::Literal(literal));
+        }
+
+        Ok(FormatTemplate {
+            literal: value.clone(),
+            segments,
+        })
+    }
+}
+
+/// Controls how the builder generates a numeric field's values
+#[derive(Debug)]
+pub enum NumericStrategy {
+    // `range = 1..=100`, counts through the range and starts over at the end
+    Range(syn::ExprRange),
+    // `start = 1000, step = 5`, either of which can be left out
+    Sequence { start: Option<Box<Expr>>, step: Option<Box<Expr>> },
+    // `cycle = [10, 20, 30]`, repeats the given values
+    Cycle(syn::ExprArray),
+}
+
+impl NumericStrategy {
+    // the attribute key, used in error messages
+    pub fn key(&self) -> &'static str {
+        match self {
+            NumericStrategy::Range(_) => "range",
+            NumericStrategy::Sequence { start: Some(_), .. } => "start",
+            NumericStrategy::Sequence { .. } => "step",
+            NumericStrategy::Cycle(_) => "cycle",
+        }
+    }
+
+    pub fn tokens(&self) -> &dyn ToTokens {
+        match self {
+            NumericStrategy::Range(range) => range,
+            NumericStrategy::Sequence { start: Some(start), .. } => start,
+            NumericStrategy::Sequence { step, .. } => step,
+            NumericStrategy::Cycle(values) => values,
+        }
+    }
+}
+
+/// Settings read from the attributes placed above a field
+#[derive(Debug, Default)]
+pub struct FieldAttributes {
+    pub is_ignored: bool,
+    pub option: Option<OptionStrategy>,
+    // overrides the name used for a field's builder functions, i.e. `set_{name}`
+    pub name: Option<proc_macro2::Ident>,
+    // allows a borrowed field to be backed by leaked allocations
+    pub leak: bool,
+    // number of elements a collection field is populated with
+    pub len: Option<Expr>,
+    // replaces the field's dynamic default, either a closure or an expression using `i`
+    pub default: Option<Expr>,
+    // path to a function that generates the field's values
+    pub with: Option<syn::ExprPath>,
+    // template used for the field's string-like values
+    pub format: Option<FormatTemplate>,
+    // kind of realistic looking value from the runtime's fake catalog, i.e. `"email"`
+    pub fake: Option<syn::LitStr>,
+    // pattern the field's string-like values match
+    pub regex: Option<RegexPattern>,
+    // how the field's numeric values are generated
+    pub numeric: Option<NumericStrategy>,
+    // what happens when a numeric value doesn't fit in the field's type
+    pub overflow: Option<OverflowPolicy>,
+    // makes the field start with its type's edge cases, holds the attribute's key
+    pub edge_cases: Option<Ident>,
+}
+
+impl FieldAttributes {
+    pub fn from_attributes(attributes: &[Attribute]) -> syn::Result<FieldAttributes> {
+        let mut field_attributes = FieldAttributes::default();
+
+        for attribute in attributes {
+            if attribute.path.is_ident("tlayuda_ignore") {
+                if let Ok(Meta::Path(_)) = attribute.parse_meta() {
+                    field_attributes.is_ignored = true;
+                }
+            } else if attribute.path.is_ident("tlayuda") {
+                for argument in parse_arguments(attribute)? {
+                    match argument.key.to_string().as_str() {
+                        "option" => {
+                            field_attributes.option = Some(parse_option_strategy(&argument)?)
+                        }
+                        "name" => field_attributes.name = Some(string_value(&argument)?.parse()?),
+                        "leak" => field_attributes.leak = true,
+                        "len" => field_attributes.len = Some(expr_value(&argument)?.clone()),
+                        "default" => {
+                            field_attributes.default = Some(expr_value(&argument)?.clone())
+                        }
+                        "with" => field_attributes.with = Some(path_value(&argument)?),
+                        "format" => {
+                            field_attributes.format =
+                                Some(FormatTemplate::parse(&string_value(&argument)?)?)
+                        }
+                        "fake" => field_attributes.fake = Some(parse_fake_kind(&argument)?),
+                        "edge_cases" => field_attributes.edge_cases = Some(argument.key),
+                        "regex" => {
+                            field_attributes.regex =
+                                Some(RegexPattern::parse(&string_value(&argument)?)?)
+                        }
+                        "overflow" => {
+                            field_attributes.overflow = Some(parse_overflow_policy(&argument)?)
+                        }
+                        "range" | "start" | "step" | "cycle" => {
+                            field_attributes.numeric =
+                                Some(parse_numeric_strategy(&argument, field_attributes.numeric.take())?)
+                        }
+                        _ => {
+                            return Err(syn::Error::new(
+                                argument.key.span(),
+                                format!("Unknown tlayuda attribute `{}`", argument.key),
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+
+        // each of these decides how the field's values are generated, so only one can be
+        // used and none of them apply to ignored fields
+        let generators = vec![
+            ("default", field_attributes.default.as_ref().map(|d| d as &dyn ToTokens)),
+            ("with", field_attributes.with.as_ref().map(|w| w as &dyn ToTokens)),
+            ("format", field_attributes.format.as_ref().map(|f| &f.literal as &dyn ToTokens)),
+            ("fake", field_attributes.fake.as_ref().map(|f| f as &dyn ToTokens)),
+            ("regex", field_attributes.regex.as_ref().map(|r| &r.literal as &dyn ToTokens)),
+            match &field_attributes.numeric {
+                Some(numeric) => (numeric.key(), Some(numeric.tokens())),
+                None => ("range", None),
+            },
+        ]
+        .into_iter()
+        .filter_map(|(key, tokens)| tokens.map(|tokens| (key, tokens)))
+        .collect::<Vec<_>>();
+
+        if let (true, Some((key, tokens))) = (field_attributes.is_ignored, generators.first()) {
+            return Err(syn::Error::new_spanned(
+                tokens,
+                format!("`{}` can't be used on a field marked with tlayuda_ignore", key),
+            ));
+        }
+
+        if let (true, Some(key)) = (field_attributes.is_ignored, &field_attributes.edge_cases) {
+            return Err(syn::Error::new(
+                key.span(),
+                "`edge_cases` can't be used on a field marked with tlayuda_ignore",
+            ));
+        }
+
+        if let [(first, _), (second, tokens), ..] = generators.as_slice() {
+            return Err(syn::Error::new_spanned(
+                tokens,
+                format!("`{}` can't be combined with `{}`", second, first),
+            ));
+        }
+
+        // `option` wraps the values the field would otherwise get, which a `default` or
+        // `with` replaces along with the Option itself
+        if let (Some(_), Some((key, tokens))) = (
+            field_attributes.option,
+            generators.iter().find(|(key, _)| *key == "default" || *key == "with"),
+        ) {
+            return Err(syn::Error::new_spanned(
+                tokens,
+                format!("`{}` can't be combined with `option`", key),
+            ));
+        }
+
+        Ok(field_attributes)
+    }
+
+    /// Whether an attribute decides how the field's values are generated
+    pub fn has_generator(&self) -> bool {
+        self.default.is_some()
+            || self.with.is_some()
+            || self.format.is_some()
+            || self.fake.is_some()
+            || self.regex.is_some()
+            || self.numeric.is_some()
+            || self.option.is_some()
+    }
+}
+
+fn parse_arguments(attribute: &Attribute) -> syn::Result<Vec<TlayudaArgument>> {
+    let arguments = attribute
+        .parse_args_with(Punctuated::<TlayudaArgument, Token![,]>::parse_terminated)?;
+
+    Ok(arguments.into_iter().collect())
+}
+
+fn expr_value(argument: &TlayudaArgument) -> syn::Result<&Expr> {
+    argument.value.as_ref().ok_or_else(|| {
+        syn::Error::new(
+            argument.key.span(),
+            format!("`{}` requires a value, e.g. `{} = ...`", argument.key, argument.key),
+        )
+    })
+}
+
+fn path_value(argument: &TlayudaArgument) -> syn::Result<syn::ExprPath> {
+    match expr_value(argument)? {
+        Expr::Path(path) => Ok(path.clone()),
+        expr => Err(syn::Error::new_spanned(expr, "Expected a path to a function")),
+    }
+}
+
+fn string_value(argument: &TlayudaArgument) -> syn::Result<syn::LitStr> {
+    match &argument.value {
+        Some(Expr::Lit(expr)) => match &expr.lit {
+            Lit::Str(value) => Ok(value.clone()),
+            _ => Err(syn::Error::new_spanned(expr, "Expected a string literal")),
+        },
+        Some(expr) => Err(syn::Error::new_spanned(expr, "Expected a string literal")),
+        None => Err(syn::Error::new(
+            argument.key.span(),
+            format!("`{}` requires a value, e.g. `{} = \"...\"`", argument.key, argument.key),
+        )),
+    }
+}
+
+fn parse_overflow_policy(argument: &TlayudaArgument) -> syn::Result<OverflowPolicy> {
+    let value = string_value(argument)?;
+    match value.value().as_str() {
+        "wrap" => Ok(OverflowPolicy::Wrap),
+        "saturate" => Ok(OverflowPolicy::Saturate),
+        "panic" => Ok(OverflowPolicy::Panic),
+        _ => Err(syn::Error::new(
+            value.span(),
+            "Expected one of \"wrap\", \"saturate\" or \"panic\"",
+        )),
+    }
+}
+
+/// The kinds of values in the runtime's fake catalog, matching its function names
+const FAKE_KINDS: &[&str] = &[
+    "first_name",
+    "last_name",
+    "full_name",
+    "email",
+    "phone",
+    "street_address",
+    "company",
+    "lorem",
+    "url",
+    "hex_color",
+];
+
+fn parse_fake_kind(argument: &TlayudaArgument) -> syn::Result<syn::LitStr> {
+    let value = string_value(argument)?;
+    if !FAKE_KINDS.contains(&value.value().as_str()) {
+        return Err(syn::Error::new(
+            value.span(),
+            format!(
+                "Unknown fake kind `{}`, expected one of: {}",
+                value.value(),
+                FAKE_KINDS.join(", ")
+            ),
+        ));
+    }
+
+    Ok(value)
+}
+
+// Parses a numeric attribute, merging `start` and `step` into the same sequence
+fn parse_numeric_strategy(
+    argument: &TlayudaArgument,
+    current: Option<NumericStrategy>,
+) -> syn::Result<NumericStrategy> {
+    let value = expr_value(argument)?.clone();
+    let strategy = match (argument.key.to_string().as_str(), value, current) {
+        ("start", start, None) => NumericStrategy::Sequence { start: Some(Box::new(start)), step: None },
+        ("start", start, Some(NumericStrategy::Sequence { step, .. })) => {
+            NumericStrategy::Sequence { start: Some(Box::new(start)), step }
+        }
+        ("step", step, None) => NumericStrategy::Sequence { start: None, step: Some(Box::new(step)) },
+        ("step", step, Some(NumericStrategy::Sequence { start, .. })) => {
+            NumericStrategy::Sequence { start, step: Some(Box::new(step)) }
+        }
+        ("range", Expr::Range(range), None) if range.from.is_some() && range.to.is_some() => {
+            check_range(&range)?;
+            NumericStrategy::Range(range)
+        }
+        ("range", value, None) => {
+            return Err(syn::Error::new_spanned(
+                value,
+                "Expected a range with a start and an end, i.e. `1..=100`",
+            ))
+        }
+        ("cycle", Expr::Array(values), None) if !values.elems.is_empty() => {
+            NumericStrategy::Cycle(values)
+        }
+        ("cycle", value, None) => {
+            return Err(syn::Error::new_spanned(
+                value,
+                "Expected an array with at least one value, i.e. `[10, 20, 30]`",
+            ))
+        }
+        (key, value, Some(current)) => {
+            return Err(syn::Error::new_spanned(
+                value,
+                format!("`{}` can't be combined with `{}`", key, current.key()),
+            ))
+        }
+        _ => unreachable!(),
+    };
+
+    Ok(strategy)
+}
+
+// Rejects a range written with literals that has fewer than two values, since it would
+// either panic or always give the same value. Ranges count by 1 from their start, so a
+// float range like `0.5..=2.5` has the values 0.5, 1.5 and 2.5.
+fn check_range(range: &syn::ExprRange) -> syn::Result<()> {
+    let bounds = (
+        range.from.as_deref().and_then(literal_value),
+        range.to.as_deref().and_then(literal_value),
+    );
+    let (start, end) = match bounds {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Ok(()),
+    };
+
+    let count = match range.limits {
+        syn::RangeLimits::HalfOpen(_) => (end - start).ceil(),
+        syn::RangeLimits::Closed(_) => (end - start).floor() + 1.0,
+    };
+    if count < 1.0 {
+        return Err(syn::Error::new_spanned(range, "`range` is empty"));
+    }
+    if count < 2.0 {
+        return Err(syn::Error::new_spanned(
+            range,
+            "`range` only has one value, use `default` for a constant value",
+        ));
+    }
+
+    Ok(())
+}
+
+// The value of a numeric literal, i.e. `5`, `-1` or `2.5`
+fn literal_value(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Int(value) => value.base10_parse().ok(),
+            Lit::Float(value) => value.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => {
+            literal_value(expr).map(|value| -value)
+        }
+        Expr::Paren(expr) => literal_value(&expr.expr),
+        _ => None,
+    }
+}
+
+fn parse_option_strategy(argument: &TlayudaArgument) -> syn::Result<OptionStrategy> {
+    let value = string_value(argument)?;
+    match value.value().as_str() {
+        "some" => Ok(OptionStrategy::Some),
+        "none" => Ok(OptionStrategy::None),
+        "alternate" => Ok(OptionStrategy::Alternate),
+        _ => Err(syn::Error::new(
+            value.span(),
+            "Expected one of \"some\", \"none\" or \"alternate\"",
+        )),
+    }
+}
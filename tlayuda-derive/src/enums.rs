@@ -0,0 +1,287 @@
+use crate::attributes::{ContainerAttributes, FieldAttributes, VariantAttributes};
+use crate::{
+    builder_generics, builder_trait_impl, builds_source, edge_case_generator, generate_initializer,
+    generate_setter_functions, seeded_generator, tlayuda_default_impl, to_snake_case, ValueContext,
+};
+use quote::quote;
+use syn::{Fields, ItemEnum, Variant};
+
+struct VariantInfo<'a> {
+    variant: &'a Variant,
+    // snake_case version of the variant name used in generated method names
+    name: String,
+    fields: Vec<VariantFieldInfo>,
+}
+
+struct VariantFieldInfo {
+    // the field's name on a struct variant, None for tuple variants
+    identifier: Option<proc_macro2::Ident>,
+    // identifier of the generator on the builder, i.e. `inner_{variant}_{field}`
+    inner_identifier: proc_macro2::Ident,
+    // used for the setter name, i.e. `set_{variant}_{field}`
+    setter_name: String,
+    field_type: syn::Type,
+    attributes: FieldAttributes,
+}
+
+pub fn derive_enum(source_enum: ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let source_enum_name = &source_enum.ident;
+    let inner_builder_name = quote::format_ident!("Tlayuda{}Builder", source_enum_name);
+    let container_attributes = ContainerAttributes::from_attributes(&source_enum.attrs)?;
+    let variants = get_variants(&source_enum)?;
+
+    if variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &source_enum.ident,
+            "Tlayuda requires at least one variant that isn't marked with tlayuda_ignore",
+        ));
+    }
+
+    let all_fields = || variants.iter().flat_map(|v| v.fields.iter());
+
+    let generics = builder_generics(
+        &source_enum.generics,
+        all_fields().map(|f| (&f.field_type, &f.attributes)),
+        &container_attributes,
+    );
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let field_declarations = all_fields().map(|field| {
+        let inner_identifier = &field.inner_identifier;
+        let field_type = &field.field_type;
+        quote! { #inner_identifier: Box<dyn FnMut(usize) -> #field_type> }
+    });
+
+    // fields that hold the enum itself are built from one of the variants that don't,
+    // i.e. `Neg(Box<Expr>)` holds a `Lit` so building it ends
+    let source_name = source_enum_name.to_string();
+    let builds_source = |variant: &VariantInfo| {
+        variant
+            .fields
+            .iter()
+            .any(|field| builds_source(&field.field_type, &field.attributes, &source_name))
+    };
+    let leaf_variants = variants
+        .iter()
+        .enumerate()
+        .filter(|(_, variant)| !builds_source(variant))
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>();
+    let leaf_builder = if variants.iter().any(builds_source) && !leaf_variants.is_empty() {
+        Some(&inner_builder_name)
+    } else {
+        None
+    };
+    let leaf_function = match leaf_builder {
+        Some(_) => quote! {
+            fn tlayuda_leaf(index: usize) -> #source_enum_name #type_generics {
+                let leaf_variants = [#(#leaf_variants),*];
+                let mut builder = Self::new().with_index(index);
+                builder.variant = Some(leaf_variants[index % leaf_variants.len()]);
+                builder.build()
+            }
+        },
+        None => quote! {},
+    };
+
+    let container_attributes = &container_attributes;
+    let source_name = &source_name;
+    let field_builder_intializers = variants.iter().flat_map(|variant| {
+        variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(move |(position, field)| {
+                let inner_identifier = &field.inner_identifier;
+                let name = match &field.identifier {
+                    Some(identifier) => identifier.to_string(),
+                    None => format!("{}_{}_", variant.name, position),
+                };
+                let context = ValueContext::new(
+                    source_name,
+                    &name,
+                    container_attributes,
+                    &field.attributes,
+                    leaf_builder,
+                );
+                let f = generate_initializer(&field.field_type, &name, &context);
+                let f = seeded_generator(f, &field.field_type, &name, &context);
+                let f = edge_case_generator(f, &field.field_type, &field.attributes);
+
+                quote! { #inner_identifier: Box::new(#f) }
+            })
+    });
+
+    let field_setter_functions = all_fields().map(|field| {
+        generate_setter_functions(
+            &field.setter_name,
+            &field.inner_identifier,
+            &field.field_type,
+            &field.attributes,
+        )
+    });
+
+    let variant_selector_functions = variants.iter().enumerate().map(|(position, variant)| {
+        let selector_func_name = quote::format_ident!("variant_{}", variant.name);
+        quote! {
+            pub fn #selector_func_name(mut self) -> Self {
+                self.variant = Some(#position);
+                self
+            }
+        }
+    });
+
+    // each built value picks the variant at index % variant_count so that
+    // build_vec spreads across every variant without any setup
+    let variant_count = variants.len();
+    let default_variant = match variant_count {
+        1 => quote! { 0 },
+        _ => quote! { self.defaults.index(i, "variant", #variant_count as u64) % #variant_count },
+    };
+    let variant_arms = variants.iter().enumerate().map(|(position, variant)| {
+        let variant_name = &variant.variant.ident;
+        let values = variant.fields.iter().map(|field| {
+            let inner_identifier = &field.inner_identifier;
+            match &field.identifier {
+                Some(identifier) => quote! { #identifier: self.#inner_identifier.as_mut()(i) },
+                None => quote! { self.#inner_identifier.as_mut()(i) },
+            }
+        });
+        let value = match variant.variant.fields {
+            Fields::Named(_) => quote! { #source_enum_name::#variant_name { #(#values),* } },
+            Fields::Unnamed(_) => quote! { #source_enum_name::#variant_name(#(#values),*) },
+            Fields::Unit => quote! { #source_enum_name::#variant_name },
+        };
+
+        if position == variant_count - 1 {
+            quote! { _ => #value, }
+        } else {
+            quote! { #position => #value, }
+        }
+    });
+
+    let builder_impl = builder_trait_impl(&inner_builder_name, source_enum_name, &generics);
+    let default_impl = tlayuda_default_impl(&inner_builder_name, source_enum_name, &generics);
+
+    let opaque = container_attributes.opaque;
+
+    let output = quote! {
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        pub struct #inner_builder_name #impl_generics #where_clause {
+            index: usize,
+            variant: Option<usize>,
+            defaults: ::tlayuda::runtime::Defaults,
+            #(#field_declarations),*
+        }
+
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        impl #impl_generics #inner_builder_name #type_generics #where_clause {
+            pub fn new() -> #inner_builder_name #type_generics {
+                let tlayuda_defaults = ::tlayuda::runtime::Defaults::new(#opaque);
+                #inner_builder_name {
+                    index: 0,
+                    variant: None,
+                    #(#field_builder_intializers,)*
+                    defaults: tlayuda_defaults,
+                }
+            }
+
+            #(#variant_selector_functions)*
+
+            #(#field_setter_functions)*
+
+            pub fn with_index(mut self, index: usize) -> Self {
+                self.index = index;
+                self
+            }
+
+            pub fn with_seed(self, seed: u64) -> Self {
+                self.defaults.set_seed(seed);
+                self
+            }
+
+            pub fn with_edge_cases(self) -> Self {
+                self.defaults.set_edge_cases();
+                self
+            }
+
+            fn take_index(&mut self) -> usize {
+                let index = self.index;
+                self.index = self.index.wrapping_add(1);
+                index
+            }
+
+            #leaf_function
+
+            pub fn build(&mut self) -> #source_enum_name #type_generics {
+                let i = self.take_index();
+                match self.variant.unwrap_or(#default_variant) {
+                    #(#variant_arms)*
+                }
+            }
+
+            pub fn build_vec(&mut self, count: usize) -> Vec::<#source_enum_name #type_generics> {
+                std::iter::repeat_with(|| self.build()).take(count).collect()
+            }
+        }
+
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        impl #impl_generics #source_enum_name #type_generics #where_clause {
+            pub fn tlayuda() -> #inner_builder_name #type_generics {
+                #inner_builder_name::new()
+            }
+        }
+
+        #builder_impl
+
+        #default_impl
+    };
+
+    Ok(output)
+}
+
+// Returns the variants that can be generated, skipping any marked with tlayuda_ignore
+fn get_variants(item_enum: &ItemEnum) -> syn::Result<Vec<VariantInfo<'_>>> {
+    let mut variants = Vec::new();
+
+    for variant in &item_enum.variants {
+        if VariantAttributes::from_attributes(&variant.attrs)?.is_ignored {
+            continue;
+        }
+
+        let name = to_snake_case(&variant.ident.to_string());
+        let mut fields = Vec::new();
+
+        for (position, field) in variant.fields.iter().enumerate() {
+            let attributes = FieldAttributes::from_attributes(&field.attrs)?;
+            if attributes.is_ignored {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "tlayuda_ignore is not supported on variant fields, ignore the variant instead",
+                ));
+            }
+
+            let field_name = match (&attributes.name, &field.ident) {
+                (Some(name), _) => name.to_string(),
+                (None, Some(identifier)) => identifier.to_string(),
+                (None, None) => position.to_string(),
+            };
+
+            fields.push(VariantFieldInfo {
+                identifier: field.ident.clone(),
+                inner_identifier: quote::format_ident!("inner_{}_{}", name, field_name),
+                setter_name: format!("{}_{}", name, field_name),
+                field_type: field.ty.clone(),
+                attributes,
+            });
+        }
+
+        variants.push(VariantInfo {
+            variant,
+            name,
+            fields,
+        });
+    }
+
+    Ok(variants)
+}
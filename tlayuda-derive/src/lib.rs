@@ -0,0 +1,1312 @@
+//! The derive macro behind [tlayuda](https://crates.io/crates/tlayuda).
+//!
+//! Use it through the `tlayuda` crate, which re-exports the macro along with the
+//! runtime support that the generated builders rely on.
+
+mod attributes;
+mod enums;
+mod regex;
+
+use attributes::{
+    ContainerAttributes, FieldAttributes, FormatSegment, NumericStrategy, OptionStrategy,
+    OverflowPolicy,
+};
+use proc_macro::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, Item, ItemStruct, Type};
+
+/// A derive macro that generates a test data builder for a struct or enum
+#[proc_macro_derive(Tlayuda, attributes(tlayuda_ignore, tlayuda))]
+pub fn entry_point(input: TokenStream) -> TokenStream {
+    let output = match parse_macro_input!(input as Item) {
+        Item::Struct(source_struct) => derive_struct(source_struct),
+        Item::Enum(source_enum) => enums::derive_enum(source_enum),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "Tlayuda can only be derived for structs and enums",
+        )),
+    };
+
+    match output {
+        Ok(output) => TokenStream::from(output),
+        Err(error) => TokenStream::from(error.to_compile_error()),
+    }
+}
+
+fn derive_struct(source_struct: ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let source_struct_name = source_struct.ident.clone();
+    let container_attributes = ContainerAttributes::from_attributes(&source_struct.attrs)?;
+    let fields = get_fields(&source_struct)?;
+    let inner_builder_name = quote::format_ident!("Tlayuda{}Builder", source_struct_name);
+
+    let generics = builder_generics(
+        &source_struct.generics,
+        fields.iter().map(|f| (&f.field_type, &f.attributes)),
+        &container_attributes,
+    );
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let OutputTokenPartials {
+        field_declarations,
+        field_builder_intializers,
+        field_setter_functions,
+    } = generate_output_tokens(&source_struct_name, &fields, &container_attributes);
+
+    let builder_parameters = fields
+        .iter()
+        .filter(|f| f.is_ignored)
+        .map(|f| {
+            let identifier = f.parameter_identifier();
+            let field_type = &f.field_type;
+
+            quote! { #identifier: #field_type }
+        })
+        .collect::<Vec<_>>();
+
+    let (ignored_fields, fields): (Vec<_>, Vec<_>) = fields.iter().partition(|f| f.is_ignored);
+
+    // Ignored fields will be manually populated by the user with a clonable
+    // instance passed into the initial .tlayuda() call. The following
+    // is intended to create those parameters.
+    let inner_builder_constructor_parameters = ignored_fields.iter()
+                                                             .map(|f| f.parameter_identifier());
+    let ignored_fields = ignored_fields.iter()
+                                       .map(|f| {
+                                           let inner_identifier = f.inner_identifier();
+                                           let member = &f.member;
+                                           quote! { #member: self.#inner_identifier.clone(), }
+                                       });
+
+    let fields = fields.iter()
+                       .map(|f| {
+                           let inner_identifier = f.inner_identifier();
+                           let member = &f.member;
+                           quote! { #member: self.#inner_identifier.as_mut()(i), }
+                       });
+
+    // structs that can be built without any parameters implement the Tlayuda trait and
+    // can be nested in other Tlayuda types, which build them through TlayudaDefault
+    let builder_impl = builder_trait_impl(&inner_builder_name, &source_struct_name, &generics);
+    let default_impl = if builder_parameters.is_empty() {
+        tlayuda_default_impl(&inner_builder_name, &source_struct_name, &generics)
+    } else {
+        quote! {}
+    };
+
+    let opaque = container_attributes.opaque;
+
+    let output = quote! {
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        pub struct #inner_builder_name #impl_generics #where_clause {
+            index: usize,
+            defaults: ::tlayuda::runtime::Defaults,
+            #(#field_declarations),*
+        }
+
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        impl #impl_generics #inner_builder_name #type_generics #where_clause {
+            pub fn new(#(#builder_parameters),*) -> #inner_builder_name #type_generics {
+                let tlayuda_defaults = ::tlayuda::runtime::Defaults::new(#opaque);
+                #inner_builder_name {
+                    index: 0,
+                    #(#field_builder_intializers,)*
+                    defaults: tlayuda_defaults,
+                }
+            }
+
+            #(#field_setter_functions)*
+
+            pub fn with_index(mut self, index: usize) -> Self {
+                self.index = index;
+                self
+            }
+
+            pub fn with_seed(self, seed: u64) -> Self {
+                self.defaults.set_seed(seed);
+                self
+            }
+
+            pub fn with_edge_cases(self) -> Self {
+                self.defaults.set_edge_cases();
+                self
+            }
+
+            fn take_index(&mut self) -> usize {
+                let index = self.index;
+                self.index = self.index.wrapping_add(1);
+                index
+            }
+
+            pub fn build(&mut self) -> #source_struct_name #type_generics {
+                let i = self.take_index();
+                #source_struct_name {
+                    #(#ignored_fields)*
+                    #(#fields)*
+                }
+            }
+
+            pub fn build_vec(&mut self, count: usize) -> Vec::<#source_struct_name #type_generics> {
+                std::iter::repeat_with(|| self.build()).take(count).collect()
+            }
+        }
+
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        impl #impl_generics #source_struct_name #type_generics #where_clause {
+            pub fn tlayuda(#(#builder_parameters),*) -> #inner_builder_name #type_generics {
+                #inner_builder_name::new(#(#inner_builder_constructor_parameters),* )
+            }
+        }
+
+        #builder_impl
+
+        #default_impl
+    };
+
+    Ok(output)
+}
+
+// Implements the runtime's TlayudaBuilder trait by forwarding to the builder's own methods
+fn builder_trait_impl(
+    builder_name: &syn::Ident,
+    source_name: &syn::Ident,
+    generics: &syn::Generics,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        impl #impl_generics ::tlayuda::runtime::TlayudaBuilder for #builder_name #type_generics #where_clause {
+            type Output = #source_name #type_generics;
+
+            fn with_index(self, index: usize) -> Self {
+                self.with_index(index)
+            }
+
+            fn with_seed(self, seed: u64) -> Self {
+                self.with_seed(seed)
+            }
+
+            fn with_edge_cases(self) -> Self {
+                self.with_edge_cases()
+            }
+
+            fn build(&mut self) -> Self::Output {
+                self.build()
+            }
+
+            fn build_vec(&mut self, count: usize) -> Vec<Self::Output> {
+                self.build_vec(count)
+            }
+        }
+    }
+}
+
+// Implements the Tlayuda and TlayudaDefault traits for a derived type that can be
+// built without any parameters
+fn tlayuda_default_impl(
+    builder_name: &syn::Ident,
+    source_name: &syn::Ident,
+    generics: &syn::Generics,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        impl #impl_generics ::tlayuda::runtime::Tlayuda for #source_name #type_generics #where_clause {
+            type Builder = #builder_name #type_generics;
+
+            fn tlayuda() -> Self::Builder {
+                #builder_name::new()
+            }
+        }
+
+        #[cfg(any(test, feature="allow_outside_tests"))]
+        impl #impl_generics ::tlayuda::runtime::TlayudaDefault for #source_name #type_generics #where_clause {
+            fn tlayuda_default(index: usize, _field_name: &str) -> Self {
+                Self::tlayuda().with_index(index).build()
+            }
+        }
+    }
+}
+
+// Adds the bounds the builder needs to the source item's generics. Type parameters
+// must be 'static since generators are boxed, type parameters whose values are built,
+// i.e. `T` in `[T; N]`, require `TlayudaDefault`, and ignored fields using one must be `Clone`.
+fn builder_generics<'a>(
+    generics: &syn::Generics,
+    field_types: impl Iterator<Item = (&'a syn::Type, &'a FieldAttributes)>,
+    container_attributes: &ContainerAttributes,
+) -> syn::Generics {
+    let mut generics = generics.clone();
+    let type_parameters = generics.type_params().map(|t| t.ident.clone()).collect::<Vec<_>>();
+    if type_parameters.is_empty() && container_attributes.bound.is_empty() {
+        return generics;
+    }
+
+    let mut predicates: Vec<syn::WherePredicate> = type_parameters
+        .iter()
+        .map(|t| syn::parse_quote! { #t: 'static })
+        .collect();
+
+    let mut built_parameters = Vec::new();
+    for (field_type, attributes) in field_types {
+        if attributes.is_ignored {
+            if mentions_any(field_type, &type_parameters) {
+                predicates.push(syn::parse_quote! { #field_type: Clone });
+            }
+            continue;
+        }
+
+        built_type_parameters(
+            field_type,
+            attributes.len.as_ref(),
+            &type_parameters,
+            &mut built_parameters,
+        );
+    }
+
+    for ident in built_parameters {
+        predicates.push(syn::parse_quote! { #ident: ::tlayuda::runtime::TlayudaDefault });
+    }
+
+    predicates.extend(container_attributes.bound.iter().cloned());
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+// Collects the type parameters whose values are built when generating a type, i.e. `T` in
+// `Option<(T, u8)>`. Collections only build their elements when they're given a `len`.
+fn built_type_parameters(
+    field_type: &syn::Type,
+    collection_len: Option<&syn::Expr>,
+    type_parameters: &[syn::Ident],
+    built_parameters: &mut Vec<syn::Ident>,
+) {
+    let mut collect = |field_type: &syn::Type, collection_len| {
+        built_type_parameters(
+            field_type,
+            collection_len,
+            type_parameters,
+            built_parameters,
+        )
+    };
+
+    match parse_field_type(field_type) {
+        FieldType::Basic(ident, _) => {
+            if type_parameters.contains(&ident) && !built_parameters.contains(&ident) {
+                built_parameters.push(ident);
+            }
+        }
+        FieldType::Option(inner_type) | FieldType::Wrapper(inner_type) => {
+            collect(&inner_type, collection_len)
+        }
+        FieldType::Reference(type_reference) => collect(&type_reference.elem, collection_len),
+        FieldType::Collection(element_type) if collection_len.is_some() => {
+            collect(&element_type, None)
+        }
+        FieldType::Map(key_type, value_type) if collection_len.is_some() => {
+            collect(&key_type, None);
+            collect(&value_type, None);
+        }
+        FieldType::Tuple(element_types) => element_types
+            .iter()
+            .for_each(|element_type| collect(element_type, None)),
+        FieldType::Array(type_array) => collect(&type_array.elem, None),
+        FieldType::Collection(_) | FieldType::Map(..) | FieldType::Other => {}
+    }
+}
+
+// Whether any of the given identifiers appear within a type, i.e. `T` in `Vec<T>`
+fn mentions_any(field_type: &syn::Type, identifiers: &[syn::Ident]) -> bool {
+    fn walk(tokens: proc_macro2::TokenStream, identifiers: &[syn::Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => identifiers.contains(&ident),
+            proc_macro2::TokenTree::Group(group) => walk(group.stream(), identifiers),
+            _ => false,
+        })
+    }
+
+    walk(field_type.into_token_stream(), identifiers)
+}
+
+// Whether a type holds the source item, i.e. `Box<Expr>` within `Expr`
+fn holds_source(field_type: &syn::Type, source_name: &str) -> bool {
+    mentions_any(field_type, &[syn::Ident::new(source_name, proc_macro2::Span::call_site())])
+}
+
+// Whether generating a field's value builds the source item again. Fields with their own
+// `default` or `with` are left to it, and optional fields holding it start out as `None`.
+fn builds_source(field_type: &syn::Type, attributes: &FieldAttributes, source_name: &str) -> bool {
+    attributes.default.is_none()
+        && attributes.with.is_none()
+        && !matches!(parse_field_type(field_type), FieldType::Option(_))
+        && holds_source(field_type, source_name)
+}
+
+#[derive(Debug)]
+struct FieldInfo {
+    // the field on the source struct, either a name or a position for tuple structs
+    member: syn::Member,
+    // used when naming the builder's functions and storage, i.e. `set_{name}`
+    name: String,
+    // prefix used for string-like dynamic defaults
+    value_name: String,
+    field_type: syn::Type,
+    is_ignored: bool,
+    attributes: FieldAttributes,
+}
+
+impl FieldInfo {
+    fn inner_identifier(&self) -> proc_macro2::Ident {
+        quote::format_ident!("inner_{}", self.name)
+    }
+
+    // the name of the `tlayuda()` parameter used for ignored fields
+    fn parameter_identifier(&self) -> proc_macro2::Ident {
+        match &self.member {
+            syn::Member::Named(identifier) if self.attributes.name.is_none() => identifier.clone(),
+            _ if self.name.starts_with(|c: char| c.is_ascii_digit()) => {
+                quote::format_ident!("field_{}", self.name)
+            }
+            _ => quote::format_ident!("{}", self.name),
+        }
+    }
+}
+
+fn get_fields(item_struct: &ItemStruct) -> syn::Result<Vec<FieldInfo>> {
+    let field_count = item_struct.fields.len();
+    let struct_name = to_snake_case(&item_struct.ident.to_string());
+
+    item_struct
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(position, x)| {
+            let attributes = FieldAttributes::from_attributes(&x.attrs)?;
+            let member = match &x.ident {
+                Some(identifier) => syn::Member::Named(identifier.clone()),
+                None => syn::Member::Unnamed(syn::Index::from(position)),
+            };
+
+            // tuple fields are set by position unless they're given a name. A newtype
+            // uses the struct's name for its values so that it behaves like its inner type
+            let name = match (&attributes.name, &x.ident) {
+                (Some(name), _) => name.to_string(),
+                (None, Some(identifier)) => identifier.to_string(),
+                (None, None) => position.to_string(),
+            };
+            let value_name = match (&attributes.name, &x.ident) {
+                (None, None) if field_count == 1 => struct_name.clone(),
+                (None, None) => format!("{}_{}_", struct_name, position),
+                _ => name.clone(),
+            };
+
+            Ok(FieldInfo {
+                member,
+                name,
+                value_name,
+                field_type: x.ty.clone(),
+                is_ignored: attributes.is_ignored,
+                attributes,
+            })
+        })
+        .collect()
+}
+
+struct OutputTokenPartials {
+    field_setter_functions: Vec<proc_macro2::TokenStream>,
+    field_builder_intializers: Vec<proc_macro2::TokenStream>,
+    field_declarations: Vec<proc_macro2::TokenStream>,
+}
+
+fn generate_output_tokens(
+    source_struct_name: &syn::Ident,
+    fields: &[FieldInfo],
+    container_attributes: &ContainerAttributes,
+) -> OutputTokenPartials {
+    let field_setter_functions = fields
+        .iter()
+        .filter(|f| !f.is_ignored)
+        .map(|field| {
+            generate_setter_functions(
+                &field.name,
+                &field.inner_identifier(),
+                &field.field_type,
+                &field.attributes,
+            )
+        })
+        .collect();
+
+    let field_builder_intializers = fields
+        .iter()
+        .map(|field| {
+            let inner_identifier = field.inner_identifier();
+
+            if field.is_ignored {
+                let value = field.parameter_identifier();
+                quote! { #inner_identifier: #value }
+            } else {
+                let source_name = source_struct_name.to_string();
+                let context = ValueContext::new(
+                    &source_name,
+                    &field.value_name,
+                    container_attributes,
+                    &field.attributes,
+                    None,
+                );
+                let f = generate_initializer(&field.field_type, &field.value_name, &context);
+                let f = seeded_generator(f, &field.field_type, &field.value_name, &context);
+                let f = edge_case_generator(f, &field.field_type, &field.attributes);
+
+                quote! { #inner_identifier: Box::new(#f) }
+            }
+        })
+        .collect();
+
+    let field_declarations = fields
+        .iter()
+        .map(
+            |field @ FieldInfo {
+                 field_type,
+                 is_ignored,
+                 ..
+             }| {
+                let identifier = field.inner_identifier();
+                if *is_ignored {
+                    quote! { #identifier: #field_type }
+                } else {
+                    quote! {
+                        #identifier: Box<dyn FnMut(usize) -> #field_type>
+                    }
+                }
+            },
+        )
+        .collect();
+
+    OutputTokenPartials {
+        field_declarations,
+        field_builder_intializers,
+        field_setter_functions,
+    }
+}
+
+// Produces the `set_` functions for a field whose generator is stored on the builder
+// as `identifier`. The functions are named using `setter_name`, i.e. `set_{setter_name}`.
+fn generate_setter_functions(
+    setter_name: &str,
+    identifier: &proc_macro2::Ident,
+    field_type: &syn::Type,
+    attributes: &FieldAttributes,
+) -> proc_macro2::TokenStream {
+    let set_func_name = quote::format_ident!("set_{}", setter_name);
+
+    // populated collections can be set per element with a closure that receives
+    // both the built object's index and the element's position
+    let element_type = match (&attributes.len, parse_field_type(field_type)) {
+        (Some(_), FieldType::Collection(element_type)) => Some(quote! { #element_type }),
+        (Some(_), FieldType::Map(key_type, value_type)) => Some(quote! { (#key_type, #value_type) }),
+        _ => None,
+    };
+    let each_setter = match (&attributes.len, element_type) {
+        (Some(len), Some(element_type)) => {
+            let set_each_func_name = quote::format_ident!("set_{}_each", setter_name);
+
+            quote! {
+                pub fn #set_each_func_name<F: 'static>(mut self, f: F) -> Self where
+                    F: Fn(usize, usize) -> #element_type {
+                        self.#identifier = Box::new(move |i| (0..#len).map(|position| f(i, position)).collect());
+                        self
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
+    let option_setters = match parse_field_type(field_type) {
+        FieldType::Option(inner_type) => {
+            let set_some_func_name = quote::format_ident!("set_{}_some", setter_name);
+            let set_none_func_name = quote::format_ident!("set_{}_none", setter_name);
+
+            quote! {
+                pub fn #set_some_func_name<F: 'static>(mut self, f: F) -> Self where
+                    F: Fn(usize) -> #inner_type {
+                        self.#identifier = Box::new(move |i| Some(f(i)));
+                        self
+                }
+
+                pub fn #set_none_func_name(mut self) -> Self {
+                    self.#identifier = Box::new(|_| None);
+                    self
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
+    quote! {
+        pub fn #set_func_name<F: 'static>(mut self, f: F) -> Self where
+            F: Fn(usize) -> #field_type {
+                self.#identifier = Box::new(f);
+                self
+        }
+
+        #option_setters
+
+        #each_setter
+    }
+}
+
+// Details about the source item and field that are needed while generating values
+#[derive(Clone, Copy)]
+struct ValueContext<'a> {
+    // names used by the `{struct}` and `{field}` placeholders of a `format` attribute
+    source_name: &'a str,
+    field_name: &'a str,
+    container_attributes: &'a ContainerAttributes,
+    attributes: &'a FieldAttributes,
+    // number of elements to populate a collection with, only applies to the field itself
+    collection_len: Option<&'a syn::Expr>,
+    // builder that can build the source item without building it again, only enums with a
+    // variant that doesn't hold the enum have one
+    leaf_builder: Option<&'a syn::Ident>,
+    // whether values are built for a builder with a seed, which makes bools vary
+    seeded: bool,
+}
+
+impl<'a> ValueContext<'a> {
+    fn new(
+        source_name: &'a str,
+        field_name: &'a str,
+        container_attributes: &'a ContainerAttributes,
+        attributes: &'a FieldAttributes,
+        leaf_builder: Option<&'a syn::Ident>,
+    ) -> ValueContext<'a> {
+        ValueContext {
+            source_name,
+            field_name,
+            container_attributes,
+            attributes,
+            collection_len: attributes.len.as_ref(),
+            leaf_builder,
+            seeded: false,
+        }
+    }
+
+    // context used for the elements of a collection, array, etc.
+    fn for_elements(&self) -> ValueContext<'a> {
+        ValueContext {
+            collection_len: None,
+            ..*self
+        }
+    }
+
+    // context used for the values built once the builder has a seed
+    fn for_seeded(&self) -> ValueContext<'a> {
+        ValueContext {
+            seeded: true,
+            ..*self
+        }
+    }
+
+    // whether borrowed values can be backed by leaked allocations
+    fn leak(&self) -> bool {
+        self.attributes.leak || self.container_attributes.leak
+    }
+
+    // what happens when a numeric value doesn't fit in the field's type, wrapping by default
+    fn overflow(&self) -> OverflowPolicy {
+        self.attributes
+            .overflow
+            .or(self.container_attributes.overflow)
+            .unwrap_or(OverflowPolicy::Wrap)
+    }
+
+    // Produces a `String` for string-like values, using the field's `format` template,
+    // `fake` kind or `regex` pattern when it has one and `{name}{index}` otherwise
+    fn string_value(&self, name: &str, index: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if let Some(pattern) = &self.attributes.regex {
+            return pattern.value(index);
+        }
+
+        if let Some(kind) = &self.attributes.fake {
+            let fake = syn::Ident::new(&kind.value(), kind.span());
+            return quote! { ::tlayuda::runtime::fake::#fake(#index) };
+        }
+
+        let template = match &self.attributes.format {
+            Some(template) => template,
+            None => return quote! { format!("{}{}", #name, #index) },
+        };
+
+        let mut format_string = String::new();
+        for segment in &template.segments {
+            let literal = match segment {
+                FormatSegment::Literal(literal) => literal.as_str(),
+                FormatSegment::Index => {
+                    format_string.push_str("{0}");
+                    continue;
+                }
+                FormatSegment::Field => self.field_name,
+                FormatSegment::Struct => self.source_name,
+            };
+            format_string.push_str(&literal.replace('{', "{{").replace('}', "}}"));
+        }
+
+        if template.segments.contains(&FormatSegment::Index) {
+            quote! { format!(#format_string, #index) }
+        } else {
+            quote! { format!(#format_string) }
+        }
+    }
+}
+
+// Produces the `|i| ...` closure a field's generator is initialized with
+fn generate_initializer(
+    field_type: &syn::Type,
+    name: &str,
+    context: &ValueContext,
+) -> proc_macro2::TokenStream {
+    let index = quote! { i };
+
+    // a `default` attribute replaces the built-in initializer. Closures are used as is
+    // and any other expression is evaluated with the index in scope as `i`
+    match &context.attributes.default {
+        Some(default @ syn::Expr::Closure(_)) => return quote! { #default },
+        Some(default) => {
+            return quote! {
+                |i| {
+                    #[allow(unused_variables)]
+                    let i: usize = i;
+                    #default
+                }
+            }
+        }
+        None => {}
+    }
+
+    // a `with` attribute calls the given function through the runtime's Generator
+    // trait, which accepts both `fn(usize) -> T` and `fn(usize, &str) -> T`
+    if let Some(with) = &context.attributes.with {
+        return quote! {
+            |i| ::tlayuda::runtime::Generator::generate(&#with, i, #name)
+        };
+    }
+
+    if let Some(template) = &context.attributes.format {
+        if !produces_any(field_type, STRING_TYPES) {
+            return syn::Error::new_spanned(
+                &template.literal,
+                "`format` can only be used on string-like fields",
+            )
+            .to_compile_error();
+        }
+    }
+
+    if let Some(kind) = &context.attributes.fake {
+        if !produces_any(field_type, STRING_TYPES) {
+            return syn::Error::new_spanned(kind, "`fake` can only be used on string-like fields")
+                .to_compile_error();
+        }
+    }
+
+    if let Some(pattern) = &context.attributes.regex {
+        if !produces_any(field_type, STRING_TYPES) {
+            return syn::Error::new_spanned(
+                &pattern.literal,
+                "`regex` can only be used on string-like fields",
+            )
+            .to_compile_error();
+        }
+    }
+
+    if let Some(numeric) = &context.attributes.numeric {
+        if !produces_any(field_type, NUMERIC_TYPES) {
+            return syn::Error::new_spanned(
+                numeric.tokens(),
+                format!("`{}` can only be used on numeric fields", numeric.key()),
+            )
+            .to_compile_error();
+        }
+    }
+
+    if let Some(len) = context.collection_len {
+        let collection_type = parse_field_type(&innermost_type(field_type));
+        if !matches!(collection_type, FieldType::Collection(..) | FieldType::Map(..)) {
+            return syn::Error::new_spanned(len, "`len` can only be used on collections")
+                .to_compile_error();
+        }
+    }
+
+    match parse_field_type(field_type) {
+        // an optional field holding the source item, i.e. the next `Node` of a `Node`,
+        // is left empty so building it ends
+        FieldType::Option(inner_type) if holds_source(&inner_type, context.source_name) => {
+            match context.attributes.option {
+                None | Some(OptionStrategy::None) => quote! { |_| None },
+                Some(_) => syn::Error::new_spanned(
+                    field_type,
+                    format!(
+                        "`option` can only be \"none\" on a field holding `{}`, building it would never end",
+                        context.source_name
+                    ),
+                )
+                .to_compile_error(),
+            }
+        }
+        FieldType::Option(inner_type) => {
+            let value = generate_value(&inner_type, name, &index, context);
+
+            match context.attributes.option.unwrap_or(OptionStrategy::Some) {
+                OptionStrategy::Some => quote! { |i| Some(#value) },
+                OptionStrategy::None => quote! { |_| None },
+                // the `None`s in between are added by `seeded_generator`, which knows the real index
+                OptionStrategy::Alternate => quote! { |i| Some(#value) },
+            }
+        }
+        _ => {
+            let value = generate_value(field_type, name, &index, context);
+            quote! { |i| #value }
+        }
+    }
+}
+
+// Wraps a field's generator so it receives the index chosen by the builder's shared
+// defaults, which is a value from the field's own stream once a seed is set. Generators
+// the user wrote in terms of the index, like `default` or `cycle`, keep the real index,
+// and so does `option = "alternate"` when picking between `Some` and `None`.
+fn seeded_generator(
+    f: proc_macro2::TokenStream,
+    field_type: &syn::Type,
+    name: &str,
+    context: &ValueContext,
+) -> proc_macro2::TokenStream {
+    let attributes = context.attributes;
+    let uses_index = attributes.default.is_some()
+        || attributes.with.is_some()
+        || matches!(
+            attributes.numeric,
+            Some(NumericStrategy::Sequence { .. }) | Some(NumericStrategy::Cycle(_))
+        );
+    let alternates = matches!(attributes.option, Some(OptionStrategy::Alternate))
+        && matches!(parse_field_type(field_type), FieldType::Option(_));
+    if uses_index && !alternates {
+        return quote! { ::tlayuda::runtime::generator::<#field_type, _>(#f) };
+    }
+
+    let (defaults, value) = if uses_index {
+        (quote! {}, quote! { f(i) })
+    } else if produces_any(field_type, &["bool"]) {
+        // bools are always `false` until there's a seed, so seeded values get their own generator
+        let seeded_f = generate_initializer(field_type, name, &context.for_seeded());
+        let limit = seeded_index_limit(field_type, context).unwrap_or_else(|| quote! { u64::MAX });
+        (
+            quote! {
+                let defaults = tlayuda_defaults.clone();
+                let seeded_f = ::tlayuda::runtime::generator::<#field_type, _>(#seeded_f);
+            },
+            quote! {
+                match defaults.seed() {
+                    Some(_) => seeded_f(defaults.index(i, #name, #limit)),
+                    None => f(i),
+                }
+            },
+        )
+    } else {
+        let limit = seeded_index_limit(field_type, context).unwrap_or_else(|| quote! { u64::MAX });
+        (
+            quote! { let defaults = tlayuda_defaults.clone(); },
+            quote! { f(defaults.index(i, #name, #limit)) },
+        )
+    };
+    let value = if alternates {
+        quote! { if i % 2 == 0 { #value } else { None } }
+    } else {
+        value
+    };
+
+    quote! {
+        {
+            #defaults
+            let f = ::tlayuda::runtime::generator::<#field_type, _>(#f);
+            move |i| #value
+        }
+    }
+}
+
+// The number of indexes a field's values can be built from without its numeric dynamic
+// defaults overflowing or its chars running out of digits, i.e. 256 for a `u8` or 64 for a
+// `[u8; 4]` whose elements use `index * 4 + position`. `None` when the field has no chars
+// or numeric types smaller than 32 bits.
+fn seeded_index_limit(
+    field_type: &syn::Type,
+    context: &ValueContext,
+) -> Option<proc_macro2::TokenStream> {
+    let per_element = |element_type: &syn::Type, len: &dyn ToTokens| {
+        seeded_index_limit(element_type, &context.for_elements())
+            .map(|limit| quote! { #limit / ((#len) as u64).max(1) })
+    };
+    let smallest = |limits: Vec<Option<proc_macro2::TokenStream>>| {
+        limits.into_iter().flatten().reduce(|a, b| quote! { #a.min(#b) })
+    };
+
+    match parse_field_type(field_type) {
+        // values from a `range` stay within it, whatever the index
+        FieldType::Basic(..) if context.attributes.numeric.is_some() => None,
+        FieldType::Basic(ident, _) => {
+            let limit: u64 = match ident.to_string().as_str() {
+                "u8" => 1 << 8,
+                "i8" => 1 << 7,
+                "u16" => 1 << 16,
+                "i16" => 1 << 15,
+                "i32" | "isize" => 1 << 31,
+                // chars are the digits of indexes below 10 and 'a' after that
+                "char" => 10,
+                _ => return None,
+            };
+            Some(quote! { #limit })
+        }
+        FieldType::Option(inner_type) | FieldType::Wrapper(inner_type) => {
+            seeded_index_limit(&inner_type, context)
+        }
+        FieldType::Reference(type_reference) => seeded_index_limit(&type_reference.elem, context),
+        FieldType::Collection(element_type) => {
+            let len = context.collection_len?;
+            per_element(&element_type, len)
+        }
+        FieldType::Map(key_type, value_type) => {
+            let len = context.collection_len?;
+            smallest(vec![per_element(&key_type, len), per_element(&value_type, len)])
+        }
+        FieldType::Array(type_array) => per_element(&type_array.elem, &type_array.len),
+        FieldType::Tuple(element_types) => smallest(
+            element_types
+                .iter()
+                .map(|element_type| seeded_index_limit(element_type, &context.for_elements()))
+                .collect(),
+        ),
+        FieldType::Other => None,
+    }
+}
+
+// Wraps a field's generator so it starts with the edge cases of the field's type, either
+// always for a field marked with `edge_cases` or once the builder's edge case mode is on.
+// The mode skips fields whose values are already decided by another attribute.
+fn edge_case_generator(
+    f: proc_macro2::TokenStream,
+    field_type: &syn::Type,
+    attributes: &FieldAttributes,
+) -> proc_macro2::TokenStream {
+    let always = attributes.edge_cases.is_some();
+    if let (Some(key), false) = (&attributes.edge_cases, has_edge_cases(field_type)) {
+        return syn::Error::new_spanned(
+            key,
+            "`edge_cases` can only be used on string, numeric, char and bool fields",
+        )
+        .to_compile_error();
+    }
+
+    if !has_edge_cases(field_type) || (!always && attributes.has_generator()) {
+        return f;
+    }
+
+    quote! {
+        {
+            let defaults = tlayuda_defaults.clone();
+            let f = #f;
+            move |i| match defaults.edge_case::<#field_type>(i, #always) {
+                Some(value) => value,
+                None => f(i),
+            }
+        }
+    }
+}
+
+// Converts a type name like `OrderPlaced` into `order_placed`
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake_case = String::new();
+    for (position, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            // a run of capitals like `HTTP` in `HTTPError` is a single word
+            let previous = position.checked_sub(1).map(|previous| chars[previous]);
+            let next = chars.get(position + 1);
+            let starts_word = match previous {
+                Some(previous) if previous.is_uppercase() => next.is_some_and(|n| n.is_lowercase()),
+                Some(_) => true,
+                None => false,
+            };
+            if starts_word {
+                snake_case.push('_');
+            }
+            snake_case.extend(c.to_lowercase());
+        } else {
+            snake_case.push(*c);
+        }
+    }
+
+    snake_case
+}
+
+// Produces an expression that evaluates to a "dynamic default" for the given type.
+// `name` is used as the prefix for string-like values and `index` is an expression
+// evaluating to the usize index of the object being built.
+fn generate_value(
+    source_type: &syn::Type,
+    name: &str,
+    index: &proc_macro2::TokenStream,
+    context: &ValueContext,
+) -> proc_macro2::TokenStream {
+    match parse_field_type(source_type) {
+        FieldType::Basic(field_type, full_field_type) => {
+            match field_type.to_string().as_str() {
+                "String" | "OsString" | "PathBuf" => {
+                    let value = context.string_value(name, index);
+                    quote! { #value.into() }
+                },
+                "char" => quote! { std::char::from_digit(#index as u32, 10).unwrap_or('a') },
+                "bool" if context.seeded => quote! { #index % 2 == 1 },
+                "bool" => quote! { false },
+                "i8" | "i16" | "i32" | "u8" | "u16" | "u32" | "i64" | "i128" | "isize"
+                | "u64" | "u128" | "usize" | "f32" | "f64" => {
+                    numeric_value(&field_type, &full_field_type, name, index, context)
+                },
+                // the source item within itself, which is built from a variant that
+                // doesn't hold it again
+                ident if ident == context.source_name => match context.leaf_builder {
+                    Some(builder) => quote! { #builder::tlayuda_leaf(#index) },
+                    None => syn::Error::new_spanned(
+                        source_type,
+                        format!(
+                            "Tlayuda can't build a `{0}` within a `{0}` without a way to stop, \
+                             use an `Option` or a `default` for this field",
+                            ident
+                        ),
+                    )
+                    .to_compile_error(),
+                },
+                // anything else, including type parameters and other Tlayuda types,
+                // is expected to implement the runtime's TlayudaDefault trait
+                _ => tlayuda_default_value(source_type, name, index),
+            }
+        },
+        FieldType::Option(inner_type) => {
+            let value = generate_value(&inner_type, name, index, context);
+            quote! { Some(#value) }
+        },
+        FieldType::Collection(element_type) => match context.collection_len {
+            Some(len) => {
+                let element = generate_value(
+                    &element_type,
+                    name,
+                    &quote! { i },
+                    &context.for_elements(),
+                );
+                populate_collection(index, len, element)
+            }
+            None => quote! { Default::default() },
+        },
+        FieldType::Map(key_type, value_type) => match context.collection_len {
+            Some(len) => {
+                let context = context.for_elements();
+                let key = generate_value(&key_type, &format!("{}_key", name), &quote! { i }, &context);
+                let value = generate_value(&value_type, &format!("{}_value", name), &quote! { i }, &context);
+                populate_collection(index, len, quote! { (#key, #value) })
+            }
+            None => quote! { Default::default() },
+        },
+        FieldType::Wrapper(inner_type) => match &inner_type {
+            // unsized contents are built as their owned equivalent and converted
+            Type::Path(type_path) if type_path.path.is_ident("str") => {
+                let value = context.string_value(name, index);
+                quote! { #value.into() }
+            },
+            Type::Path(type_path) if is_path_type(type_path) => {
+                let value = context.string_value(name, index);
+                quote! { std::path::PathBuf::from(#value).into() }
+            },
+            Type::Slice(_) => quote! { Vec::new().into() },
+            _ => {
+                let value = generate_value(&inner_type, name, index, context);
+                quote! { <#source_type>::new(#value) }
+            },
+        },
+        FieldType::Tuple(element_types) => {
+            // elements are named by position, i.e. `pair_0_3` for the first element at index 3
+            let elements = element_types.iter().enumerate().map(|(position, element_type)| {
+                let name = format!("{}_{}_", name.trim_end_matches('_'), position);
+                generate_value(element_type, &name, index, &context.for_elements())
+            });
+            quote! { (#(#elements,)*) }
+        },
+        FieldType::Reference(type_reference) => {
+            let is_mutable = type_reference.mutability.is_some();
+
+            match &*type_reference.elem {
+                // empty slices don't need any backing storage
+                Type::Slice(_) if is_mutable => quote! { &mut [] },
+                Type::Slice(_) => quote! { &[] },
+                _ if !context.leak() => syn::Error::new_spanned(
+                    source_type,
+                    "Tlayuda needs #[tlayuda(leak)] to generate borrowed values",
+                ).to_compile_error(),
+                Type::Path(type_path) if type_path.path.is_ident("str") => {
+                    let value = context.string_value(name, index);
+                    quote! { Box::leak(#value.into_boxed_str()) }
+                },
+                Type::Path(type_path) if is_path_type(type_path) => {
+                    let value = context.string_value(name, index);
+                    quote! { Box::leak(std::path::PathBuf::from(#value).into_boxed_path()) }
+                },
+                inner_type => {
+                    let value = generate_value(inner_type, name, index, context);
+                    quote! { Box::leak(Box::new(#value)) }
+                },
+            }
+        },
+        FieldType::Other => tlayuda_default_value(source_type, name, index),
+        FieldType::Array(type_array) => {
+            let len = &type_array.len;
+            let element_index = element_index(index, len);
+            let element = generate_value(
+                &type_array.elem,
+                name,
+                &quote! { i },
+                &context.for_elements(),
+            );
+
+            quote! {
+                std::array::from_fn(|position| {
+                    let i = #element_index;
+                    #element
+                })
+            }
+        }
+    }
+}
+
+// Produces a numeric value, following the field's `range`, `start`/`step` or `cycle`
+// attributes. Values are calculated as an i128 (or f64 for floats) and then converted
+// into the field's type using the overflow policy.
+fn numeric_value(
+    numeric_type: &syn::Ident,
+    full_field_type: &proc_macro2::TokenStream,
+    name: &str,
+    index: &proc_macro2::TokenStream,
+    context: &ValueContext,
+) -> proc_macro2::TokenStream {
+    let is_float = numeric_type == "f32" || numeric_type == "f64";
+    let wide_type = if is_float {
+        quote! { f64 }
+    } else {
+        quote! { i128 }
+    };
+
+    let value = match &context.attributes.numeric {
+        None => quote! { (#index) as #wide_type },
+        Some(NumericStrategy::Cycle(values)) => {
+            let values = values.elems.iter();
+            quote! {
+                {
+                    let values: &[#wide_type] = &[#((#values) as #wide_type),*];
+                    values[#index % values.len()]
+                }
+            }
+        }
+        Some(NumericStrategy::Range(range)) => {
+            // float ranges count by 1 from their start, so `0.5..=2.5` is 0.5, 1.5 and 2.5
+            let (start, end) = (&range.from, &range.to);
+            let count = match (range.limits, is_float) {
+                (syn::RangeLimits::Closed(_), false) => quote! { end - start + 1 },
+                (syn::RangeLimits::HalfOpen(_), false) => quote! { end - start },
+                (syn::RangeLimits::Closed(_), true) => quote! { (end - start).floor() + 1.0 },
+                (syn::RangeLimits::HalfOpen(_), true) => quote! { (end - start).ceil() },
+            };
+
+            quote! {
+                {
+                    let (start, end) = ((#start) as #wide_type, (#end) as #wide_type);
+                    let count = (#count) as i128;
+                    start + ::tlayuda::runtime::cycle_position(#index, count, #name) as #wide_type
+                }
+            }
+        }
+        Some(NumericStrategy::Sequence { start, step }) => {
+            let start = start.as_ref().map_or(quote! { 0 }, |start| quote! { #start });
+            let step = step.as_ref().map_or(quote! { 1 }, |step| quote! { #step });
+
+            if is_float {
+                quote! { (#start) as f64 + (#index) as f64 * (#step) as f64 }
+            } else {
+                quote! {
+                    ((#start) as i128).wrapping_add(((#index) as i128).wrapping_mul((#step) as i128))
+                }
+            }
+        }
+    };
+
+    let overflow = match context.overflow() {
+        _ if is_float => return quote! { (#value) as #full_field_type },
+        OverflowPolicy::Wrap => return quote! { (#value) as #full_field_type },
+        OverflowPolicy::Saturate => quote! { Saturate },
+        OverflowPolicy::Panic => quote! { Panic },
+    };
+
+    quote! {
+        <#full_field_type as ::tlayuda::runtime::Integer>::from_i128(
+            #value,
+            ::tlayuda::runtime::Overflow::#overflow,
+            #name,
+            #index,
+        )
+    }
+}
+
+// Builds a value through the runtime's TlayudaDefault trait
+fn tlayuda_default_value(
+    source_type: &syn::Type,
+    name: &str,
+    index: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        <#source_type as ::tlayuda::runtime::TlayudaDefault>::tlayuda_default(#index, #name)
+    }
+}
+
+// Expression for the index of the element at `position` within a collection or array of `len` elements
+fn element_index(index: &proc_macro2::TokenStream, len: &syn::Expr) -> proc_macro2::TokenStream {
+    quote! { ::tlayuda::runtime::element_index(#index, #len, position) }
+}
+
+// Produces an expression that collects `len` elements, each generated with its own index
+fn populate_collection(
+    index: &proc_macro2::TokenStream,
+    len: &syn::Expr,
+    element: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let element_index = element_index(index, len);
+
+    quote! {
+        (0..#len)
+            .map(|position| {
+                let i = #element_index;
+                #element
+            })
+            .collect()
+    }
+}
+
+// Whether a type is the unsized `std::path::Path`
+fn is_path_type(type_path: &syn::TypePath) -> bool {
+    type_path.path.segments.last().is_some_and(|segment| segment.ident == "Path")
+}
+
+const STRING_TYPES: &[&str] = &["String", "OsString", "PathBuf", "str", "Path"];
+const NUMERIC_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64",
+];
+
+// Whether the runtime has edge cases for a type, i.e. `&str` or `Option<f64>`
+fn has_edge_cases(field_type: &syn::Type) -> bool {
+    match parse_field_type(field_type) {
+        FieldType::Basic(ident, _) => ["String", "OsString", "PathBuf", "char", "bool"]
+            .iter()
+            .chain(NUMERIC_TYPES)
+            .any(|name| ident == name),
+        FieldType::Option(inner_type) => has_edge_cases(&inner_type),
+        FieldType::Reference(type_reference) => {
+            type_reference.mutability.is_none()
+                && matches!(&*type_reference.elem, Type::Path(type_path) if type_path.path.is_ident("str"))
+        }
+        _ => false,
+    }
+}
+
+// Whether a type produces any values of the named types, i.e. `String` in `Vec<Box<str>>`
+fn produces_any(field_type: &syn::Type, type_names: &[&str]) -> bool {
+    let produces = |field_type: &syn::Type| produces_any(field_type, type_names);
+
+    match parse_field_type(field_type) {
+        FieldType::Basic(ident, _) => type_names.iter().any(|name| ident == name),
+        FieldType::Option(inner_type)
+        | FieldType::Wrapper(inner_type)
+        | FieldType::Collection(inner_type) => produces(&inner_type),
+        FieldType::Map(key_type, value_type) => produces(&key_type) || produces(&value_type),
+        FieldType::Reference(type_reference) => produces(&type_reference.elem),
+        FieldType::Tuple(element_types) => element_types.iter().any(produces),
+        FieldType::Array(type_array) => produces(&type_array.elem),
+        FieldType::Other => false,
+    }
+}
+
+// Removes any layers of Options and smart pointers around a type, i.e. `Vec<T>` in `Option<Arc<Vec<T>>>`
+fn innermost_type(field_type: &syn::Type) -> syn::Type {
+    if let Type::Path(_) = field_type {
+        if let FieldType::Option(inner_type) | FieldType::Wrapper(inner_type) = parse_field_type(field_type) {
+            return innermost_type(&inner_type);
+        }
+    }
+
+    field_type.clone()
+}
+
+enum FieldType {
+    Basic(syn::Ident, proc_macro2::TokenStream),
+    Option(syn::Type),
+    // smart pointers and cells that are constructed with `new`, i.e. `Arc<T>`
+    Wrapper(syn::Type),
+    // sequences and sets, i.e. `Vec<T>` or `HashSet<T>`
+    Collection(syn::Type),
+    // maps, i.e. `HashMap<K, V>`
+    Map(Box<syn::Type>, Box<syn::Type>),
+    Reference(syn::TypeReference),
+    Tuple(Vec<syn::Type>),
+    Array(syn::TypeArray),
+    // types without a built-in default, i.e. function pointers
+    Other,
+}
+
+// Returns the generic type arguments of a path segment, i.e. `K` and `V` in `HashMap<K, V>`
+fn generic_arguments(segment: &syn::PathSegment) -> Vec<&syn::Type> {
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                syn::GenericArgument::Type(inner_type) => Some(inner_type),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_field_type(field_type: &syn::Type) -> FieldType {
+    match field_type {
+        Type::Path(type_path) => match type_path.path.get_ident() {
+            Some(ident) => FieldType::Basic(ident.clone(), ident.into_token_stream()),
+            None => {
+                let segment = type_path.path.segments.last().unwrap();
+                let ident = segment.ident.clone();
+                match (ident.to_string().as_str(), generic_arguments(segment).as_slice()) {
+                    ("Option", [inner_type]) => FieldType::Option((*inner_type).clone()),
+                    ("Box" | "Rc" | "Arc" | "Cell" | "RefCell" | "Mutex" | "RwLock", [inner_type]) => {
+                        FieldType::Wrapper((*inner_type).clone())
+                    }
+                    ("Vec" | "VecDeque" | "LinkedList" | "BinaryHeap", [element_type])
+                    | ("HashSet" | "BTreeSet", [element_type, ..]) => {
+                        FieldType::Collection((*element_type).clone())
+                    }
+                    ("HashMap" | "BTreeMap", [key_type, value_type, ..]) => {
+                        FieldType::Map(Box::new((*key_type).clone()), Box::new((*value_type).clone()))
+                    }
+                    _ => FieldType::Basic(ident, type_path.into_token_stream()),
+                }
+            }
+        },
+        Type::Array(type_array) => FieldType::Array(type_array.clone()),
+        Type::Reference(type_reference) => FieldType::Reference(type_reference.clone()),
+        Type::Tuple(type_tuple) => FieldType::Tuple(type_tuple.elems.iter().cloned().collect()),
+        Type::Paren(type_paren) => parse_field_type(&type_paren.elem),
+        _ => FieldType::Other,
+    }
+}
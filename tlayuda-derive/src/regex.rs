@@ -0,0 +1,434 @@
+//! Parsing of the patterns used by the `regex` attribute and the code generating strings
+//! that match them.
+//!
+//! Only the parts of the regex syntax that describe what a string contains are supported:
+//! literals, escapes, `.`, classes like `[a-z_]` and `\d`, groups, alternation and
+//! quantifiers. Anything that only constrains where a match can be, like `\b` or
+//! lookarounds, is rejected since every generated string is a whole match anyway.
+
+use quote::quote;
+
+// printable ascii, used for `.` and the complement of negated classes
+const PRINTABLE: (char, char) = (' ', '~');
+
+// how many extra repetitions `*`, `+` and `{n,}` can produce
+const UNBOUNDED_REPETITIONS: u32 = 8;
+
+/// A `regex` attribute's pattern
+#[derive(Debug)]
+pub struct RegexPattern {
+    pub literal: syn::LitStr,
+    node: Node,
+}
+
+#[derive(Debug)]
+enum Node {
+    Literal(String),
+    // inclusive ranges of characters, never empty
+    Class(Vec<(char, char)>),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat { node: Box<Node>, min: u32, max: u32 },
+}
+
+impl RegexPattern {
+    pub fn parse(literal: &syn::LitStr) -> syn::Result<RegexPattern> {
+        let pattern = literal.value();
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            position: 0,
+        };
+
+        let node = parser
+            .parse_alternation()
+            .and_then(|node| match parser.peek() {
+                Some(')') => Err(parser.error("Unmatched `)`")),
+                _ => Ok(node),
+            });
+
+        match node {
+            Ok(node) => Ok(RegexPattern {
+                literal: literal.clone(),
+                node,
+            }),
+            Err(message) => Err(syn::Error::new(
+                literal.span(),
+                format!("Invalid regex: {}", message),
+            )),
+        }
+    }
+
+    /// An expression building a `String` that matches the pattern, using the value of
+    /// `index` to decide each choice the pattern allows
+    pub fn value(&self, index: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let statements = statements(&self.node);
+        quote! {
+            {
+                let mut choices = ::tlayuda::runtime::Choices::new(#index);
+                let mut value = String::new();
+                #statements
+                value
+            }
+        }
+    }
+}
+
+// The statements pushing a match of `node` onto `value`
+fn statements(node: &Node) -> proc_macro2::TokenStream {
+    match node {
+        Node::Literal(literal) if literal.is_empty() => quote! {},
+        Node::Literal(literal) => quote! { value.push_str(#literal); },
+        Node::Class(ranges) => {
+            let ranges = ranges.iter().map(|(start, end)| quote! { (#start, #end) });
+            quote! { value.push(choices.char_in(&[#(#ranges),*])); }
+        }
+        Node::Concat(nodes) => {
+            let nodes = nodes.iter().map(statements);
+            quote! { #(#nodes)* }
+        }
+        Node::Alternation(nodes) => {
+            let count = nodes.len();
+            let arms = nodes
+                .iter()
+                .map(statements)
+                .enumerate()
+                .map(|(position, statements)| {
+                    if position + 1 == count {
+                        quote! { _ => { #statements } }
+                    } else {
+                        quote! { #position => { #statements } }
+                    }
+                });
+            quote! {
+                match choices.choose(#count) {
+                    #(#arms)*
+                }
+            }
+        }
+        Node::Repeat { node, min, max } => {
+            let statements = statements(node);
+            let extra = (max - min) as usize;
+            match (min, extra) {
+                (1, 0) => statements,
+                (_, 0) => quote! { for _ in 0..#min { #statements } },
+                _ => quote! {
+                    for _ in 0..#min as usize + choices.choose(#extra + 1) { #statements }
+                },
+            }
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let next = self.peek();
+        self.position += 1;
+        next
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        let matches = self.peek() == Some(expected);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at position {}", message, self.position)
+    }
+
+    fn parse_alternation(&mut self) -> Result<Node, String> {
+        let mut alternatives = vec![self.parse_concat()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_concat()?);
+        }
+
+        if alternatives.len() == 1 {
+            Ok(alternatives.remove(0))
+        } else {
+            Ok(Node::Alternation(alternatives))
+        }
+    }
+
+    fn parse_concat(&mut self) -> Result<Node, String> {
+        let mut nodes: Vec<Node> = Vec::new();
+
+        while let Some(next) = self.peek() {
+            let atom = match next {
+                '|' | ')' => break,
+                '^' if self.position == 0 => {
+                    self.position += 1;
+                    continue;
+                }
+                '$' if self.position + 1 == self.chars.len() => {
+                    self.position += 1;
+                    continue;
+                }
+                '^' | '$' => return Err(self.error("Anchors are only supported at the ends")),
+                '*' | '+' | '?' | '{' => return Err(self.error("Nothing to repeat")),
+                '(' => self.parse_group()?,
+                '[' => self.parse_class()?,
+                '.' => {
+                    self.position += 1;
+                    Node::Class(vec![PRINTABLE])
+                }
+                '\\' => self.parse_escape(false)?,
+                _ => {
+                    self.position += 1;
+                    Node::Literal(next.to_string())
+                }
+            };
+            let atom = self.parse_repetitions(atom)?;
+
+            // consecutive literals are pushed as a single string
+            match (nodes.last_mut(), &atom) {
+                (Some(Node::Literal(previous)), Node::Literal(literal)) => {
+                    previous.push_str(literal)
+                }
+                _ => nodes.push(atom),
+            }
+        }
+
+        if nodes.len() == 1 {
+            Ok(nodes.remove(0))
+        } else {
+            Ok(Node::Concat(nodes))
+        }
+    }
+
+    fn parse_repetitions(&mut self, mut node: Node) -> Result<Node, String> {
+        loop {
+            let (min, max) = match self.peek() {
+                Some('?') => (0, Some(1)),
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('{') => {
+                    self.position += 1;
+                    let min = self.parse_number()?;
+                    let max = if self.eat(',') {
+                        match self.peek() {
+                            Some('}') => None,
+                            _ => Some(self.parse_number()?),
+                        }
+                    } else {
+                        Some(min)
+                    };
+                    if self.peek() != Some('}') {
+                        return Err(self.error("Unclosed `{`"));
+                    }
+                    if max.is_some_and(|max| max < min) {
+                        return Err(self.error("Repetition has a larger minimum than maximum"));
+                    }
+                    (min, max)
+                }
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            // lazy quantifiers match the same strings
+            self.eat('?');
+
+            node = Node::Repeat {
+                node: Box::new(node),
+                min,
+                max: max.unwrap_or_else(|| min.saturating_add(UNBOUNDED_REPETITIONS)),
+            };
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<u32, String> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+
+        let digits = self.chars[start..self.position].iter().collect::<String>();
+        digits
+            .parse()
+            .map_err(|_| self.error("Expected a number in repetition"))
+    }
+
+    fn parse_group(&mut self) -> Result<Node, String> {
+        self.position += 1;
+        if self.eat('?') {
+            match self.next() {
+                Some(':') => {}
+                Some('P') if self.eat('<') => self.skip_group_name()?,
+                Some('<') if !matches!(self.peek(), Some('=') | Some('!')) => {
+                    self.skip_group_name()?
+                }
+                _ => return Err(self.error("Flags and lookarounds aren't supported")),
+            }
+        }
+
+        let node = self.parse_alternation()?;
+        if !self.eat(')') {
+            return Err(self.error("Unclosed `(`"));
+        }
+
+        Ok(node)
+    }
+
+    fn skip_group_name(&mut self) -> Result<(), String> {
+        while let Some(next) = self.next() {
+            match next {
+                '>' => return Ok(()),
+                '_' => {}
+                _ if next.is_alphanumeric() => {}
+                _ => break,
+            }
+        }
+
+        Err(self.error("Invalid group name"))
+    }
+
+    fn parse_class(&mut self) -> Result<Node, String> {
+        self.position += 1;
+        let negated = self.eat('^');
+        let mut ranges = Vec::new();
+
+        // a `]` right after the opening bracket is a literal
+        let mut first = true;
+        loop {
+            let start = match self.peek() {
+                None => return Err(self.error("Unclosed `[`")),
+                Some(']') if !first => {
+                    self.position += 1;
+                    break;
+                }
+                Some('[') if self.chars.get(self.position + 1) == Some(&':') => {
+                    return Err(self.error("Character class names aren't supported"))
+                }
+                Some('\\') => match self.parse_escape(true)? {
+                    Node::Class(class) => {
+                        ranges.extend(class);
+                        first = false;
+                        continue;
+                    }
+                    Node::Literal(literal) => literal.chars().next().unwrap_or_default(),
+                    _ => unreachable!(),
+                },
+                Some(next) => {
+                    self.position += 1;
+                    next
+                }
+            };
+            first = false;
+
+            let is_range = self.peek() == Some('-')
+                && !matches!(self.chars.get(self.position + 1), Some(']') | None);
+            if !is_range {
+                ranges.push((start, start));
+                continue;
+            }
+
+            self.position += 1;
+            let end = match self.peek() {
+                Some('\\') => match self.parse_escape(true)? {
+                    Node::Literal(literal) => literal.chars().next().unwrap_or_default(),
+                    _ => return Err(self.error("Invalid range in class")),
+                },
+                _ => self.next().unwrap_or_default(),
+            };
+            if end < start {
+                return Err(self.error("Invalid range in class"));
+            }
+            ranges.push((start, end));
+        }
+
+        let ranges = if negated { complement(&ranges) } else { ranges };
+        if ranges.is_empty() {
+            return Err(self.error("Class doesn't match any printable character"));
+        }
+
+        Ok(Node::Class(ranges))
+    }
+
+    fn parse_escape(&mut self, in_class: bool) -> Result<Node, String> {
+        self.position += 1;
+        let escaped = match self.next() {
+            Some(escaped) => escaped,
+            None => return Err(self.error("Incomplete escape")),
+        };
+
+        let literal = match escaped {
+            'd' => return Ok(Node::Class(vec![('0', '9')])),
+            'w' => {
+                return Ok(Node::Class(vec![
+                    ('0', '9'),
+                    ('A', 'Z'),
+                    ('_', '_'),
+                    ('a', 'z'),
+                ]))
+            }
+            's' => return Ok(Node::Class(vec![('\t', '\n'), ('\r', '\r'), (' ', ' ')])),
+            'D' => return Ok(Node::Class(complement(&[('0', '9')]))),
+            'W' => {
+                return Ok(Node::Class(complement(&[
+                    ('0', '9'),
+                    ('A', 'Z'),
+                    ('_', '_'),
+                    ('a', 'z'),
+                ])))
+            }
+            'S' => {
+                return Ok(Node::Class(complement(&[
+                    ('\t', '\n'),
+                    ('\r', '\r'),
+                    (' ', ' '),
+                ])))
+            }
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'x' => self.parse_hex_escape()?,
+            'b' if in_class => '\u{8}',
+            _ if escaped.is_ascii_punctuation() || escaped == ' ' => escaped,
+            _ => return Err(self.error(&format!("Unsupported escape `\\{}`", escaped))),
+        };
+
+        Ok(Node::Literal(literal.to_string()))
+    }
+
+    // `\x41` or `\x{1F600}`
+    fn parse_hex_escape(&mut self) -> Result<char, String> {
+        let braced = self.eat('{');
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_hexdigit())
+            && (braced || self.position < start + 2)
+        {
+            self.position += 1;
+        }
+        let digits = self.chars[start..self.position].iter().collect::<String>();
+        if braced && !self.eat('}') {
+            return Err(self.error("Unclosed `{` in escape"));
+        }
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(std::char::from_u32)
+            .ok_or_else(|| self.error("Invalid hex escape"))
+    }
+}
+
+// The printable ascii characters that aren't in `ranges`
+fn complement(ranges: &[(char, char)]) -> Vec<(char, char)> {
+    (PRINTABLE.0..=PRINTABLE.1)
+        .filter(|c| !ranges.iter().any(|(start, end)| (start..=end).contains(&c)))
+        .fold(Vec::new(), |mut complement: Vec<(char, char)>, c| {
+            match complement.last_mut() {
+                Some((_, end)) if *end as u32 + 1 == c as u32 => *end = c,
+                _ => complement.push((c, c)),
+            }
+            complement
+        })
+}